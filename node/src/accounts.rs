@@ -0,0 +1,119 @@
+//! Endowed accounts of the development chain.
+//!
+//! The well-known accounts, Alice to Ferdie and their stashes, are always endowed as they hold the
+//! sudo key and sign the `dev_*` RPC calls. `--dev-accounts` adds accounts derived from the
+//! development mnemonic, or from `--dev-mnemonic`, so that parallel test workers each get their
+//! own funded accounts.
+
+use sc_cli::{CryptoScheme, Error, Result};
+use sp_core::{
+	bytes::to_hex,
+	crypto::{Ss58Codec, DEV_PHRASE},
+	ecdsa, ed25519, sr25519, Get, Pair,
+};
+use sp_runtime::traits::{IdentifyAccount, Verify};
+use swanky_runtime::{AccountId, Balance, SS58Prefix, Signature};
+
+type AccountPublic = <Signature as Verify>::Signer;
+
+/// Names of the well-known development accounts.
+const WELL_KNOWN: &[&str] = &["Alice", "Bob", "Charlie", "Dave", "Eve", "Ferdie"];
+
+/// Options of the endowed accounts of the development chain.
+#[derive(Debug, Clone, clap::Args)]
+pub struct DevAccountsParams {
+	/// Number of accounts to endow besides the well-known ones, derived as `<MNEMONIC>//<INDEX>`.
+	#[clap(long, value_name = "COUNT", default_value_t = 0)]
+	pub dev_accounts: u32,
+
+	/// Mnemonic the accounts of `--dev-accounts` are derived from, the development mnemonic by
+	/// default.
+	#[clap(long, value_name = "PHRASE")]
+	pub dev_mnemonic: Option<String>,
+
+	/// Balance of every endowed account.
+	#[clap(long, value_name = "BALANCE", default_value_t = 1 << 100)]
+	pub dev_balance: Balance,
+
+	/// Key type of the accounts of `--dev-accounts`.
+	#[clap(long, value_name = "TYPE", value_enum, default_value = "sr25519")]
+	pub dev_account_type: CryptoScheme,
+}
+
+/// An endowed account of the development chain.
+pub struct DevAccount {
+	/// Name of the account, its derivation path.
+	pub name: String,
+	/// Secret URI of the account key.
+	pub uri: String,
+	/// Key type of the account.
+	pub scheme: CryptoScheme,
+	/// ID of the account.
+	pub id: AccountId,
+}
+
+impl DevAccountsParams {
+	/// Returns the endowed accounts, the well-known ones first.
+	pub fn accounts(&self) -> std::result::Result<Vec<DevAccount>, String> {
+		let well_known = WELL_KNOWN
+			.iter()
+			.map(|name| name.to_string())
+			.chain(WELL_KNOWN.iter().map(|name| format!("{}//stash", name)))
+			.map(|name| account(name, DEV_PHRASE, CryptoScheme::Sr25519));
+		let mnemonic = self.dev_mnemonic.as_deref().unwrap_or(DEV_PHRASE);
+		let generated = (0..self.dev_accounts)
+			.map(|index| account(index.to_string(), mnemonic, self.dev_account_type));
+
+		well_known.chain(generated).collect()
+	}
+}
+
+/// Derives the account `//<name>` of `mnemonic` with a key of the given type.
+fn account(
+	name: String,
+	mnemonic: &str,
+	scheme: CryptoScheme,
+) -> std::result::Result<DevAccount, String> {
+	let uri = format!("{}//{}", mnemonic, name);
+	let public = match scheme {
+		CryptoScheme::Sr25519 => AccountPublic::from(public::<sr25519::Pair>(&uri)?),
+		CryptoScheme::Ed25519 => AccountPublic::from(public::<ed25519::Pair>(&uri)?),
+		CryptoScheme::Ecdsa => AccountPublic::from(public::<ecdsa::Pair>(&uri)?),
+	};
+	Ok(DevAccount { name, uri, scheme, id: public.into_account() })
+}
+
+fn public<P: Pair>(uri: &str) -> std::result::Result<P::Public, String> {
+	P::from_string(uri, None)
+		.map(|pair| pair.public())
+		.map_err(|e| format!("Invalid development account URI: {:?}", e))
+}
+
+/// The `accounts` command used to print the endowed accounts of the development chain.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct AccountsCmd {
+	#[clap(flatten)]
+	pub accounts: DevAccountsParams,
+
+	/// SS58 prefix to display the addresses with, the one of the runtime profile by default.
+	#[clap(long, value_name = "PREFIX")]
+	pub ss58_prefix: Option<u16>,
+}
+
+impl AccountsCmd {
+	/// Print every endowed account with its addresses and secret URI.
+	///
+	/// The H160 address is the truncated account ID, as mapped by `EnsureAddressTruncated`.
+	pub fn run(&self) -> Result<()> {
+		let ss58_prefix = self.ss58_prefix.unwrap_or_else(<SS58Prefix as Get<u16>>::get);
+		for account in self.accounts.accounts().map_err(Error::Input)? {
+			let id: &[u8] = account.id.as_ref();
+			println!("//{} ({:?})", account.name, account.scheme);
+			println!("  SS58:       {}", account.id.to_ss58check_with_version(ss58_prefix.into()));
+			println!("  Hex:        {}", to_hex(id, false));
+			println!("  H160:       {}", to_hex(&id[..20], false));
+			println!("  Secret URI: {}", account.uri);
+		}
+		Ok(())
+	}
+}
@@ -1,8 +1,19 @@
-use sc_service::ChainType;
-use sp_core::{sr25519, Pair, Public};
-use sp_runtime::traits::{IdentifyAccount, Verify};
+use crate::{check_contract::read_code, cli::DevParams};
+use pallet_genesis_contracts::GenesisContract;
+use pallet_genesis_uniques::{GenesisCollection, GenesisItem};
+use sc_service::{ChainType, Properties};
+use serde::Deserialize;
+use sp_core::{crypto::Ss58Codec, sr25519, Bytes, Get, Pair, Public};
+use sp_runtime::{
+	traits::{IdentifyAccount, Verify},
+	BuildStorage,
+};
+use std::{collections::BTreeSet, fs, path::Path};
 use swanky_runtime::{
-	AccountId, BalancesConfig, GenesisConfig, Signature, SudoConfig, SystemConfig, WASM_BINARY,
+	profile, AccountId, AssetId, AssetsConfig, Balance, BalancesConfig, ChainPropertiesConfig,
+	FreeTransactionsConfig, GenesisConfig, GenesisContracts, GenesisContractsConfig,
+	GenesisUniquesConfig, PovLimitConfig, Signature, SudoConfig, SystemConfig, UniquesStringLimit,
+	WASM_BINARY,
 };
 
 // The URL for the telemetry server.
@@ -28,37 +39,281 @@ where
 	AccountPublic::from(get_from_seed::<TPublic>(seed)).into_account()
 }
 
-pub fn development_config() -> Result<ChainSpec, String> {
+/// Parses an SS58 address, defaulting to Alice.
+fn account_or_alice(address: Option<String>) -> Result<AccountId, String> {
+	match address {
+		Some(address) => AccountId::from_ss58check(&address)
+			.map_err(|e| format!("Invalid address {}: {:?}", address, e)),
+		None => Ok(get_account_id_from_seed::<sr25519::Public>("Alice")),
+	}
+}
+
+/// Contract listed in the `--genesis-contracts` file.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct GenesisContractEntry {
+	path: String,
+	constructor: Bytes,
+	#[serde(default)]
+	args: Bytes,
+	#[serde(default)]
+	salt: Bytes,
+	#[serde(default)]
+	endowment: Balance,
+	deployer: Option<String>,
+}
+
+/// Reads the contracts to instantiate at genesis from the `--genesis-contracts` file.
+///
+/// The contracts are dry-run on top of `genesis`, which the genesis build would panic on.
+fn genesis_contracts(
+	path: &Path,
+	genesis: GenesisConfig,
+) -> Result<Vec<GenesisContract<AccountId, Balance>>, String> {
+	let data = fs::read(path).map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+	let entries: Vec<GenesisContractEntry> = serde_json::from_slice(&data)
+		.map_err(|e| format!("Invalid genesis contracts {}: {}", path.display(), e))?;
+	let dir = path.parent().unwrap_or_else(|| Path::new(""));
+
+	let mut ext = sp_io::TestExternalities::new(genesis.build_storage()?);
+	entries
+		.into_iter()
+		.map(|entry| {
+			let contract = GenesisContract {
+				deployer: account_or_alice(entry.deployer)?,
+				code: read_code(&dir.join(&entry.path))?,
+				data: [entry.constructor.0, entry.args.0].concat(),
+				salt: entry.salt.0,
+				value: entry.endowment,
+			};
+			ext.execute_with(|| GenesisContracts::instantiate(&contract)).map_err(|e| {
+				format!("Genesis contract {} cannot be instantiated: {:?}", entry.path, e)
+			})?;
+			Ok(contract)
+		})
+		.collect()
+}
+
+/// Assets and collections listed in the `--genesis-tokens` file.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct GenesisTokensFile {
+	#[serde(default)]
+	assets: Vec<GenesisAssetEntry>,
+	#[serde(default)]
+	collections: Vec<GenesisCollectionEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct GenesisAssetEntry {
+	id: AssetId,
+	owner: Option<String>,
+	name: String,
+	symbol: String,
+	decimals: u8,
+	min_balance: Balance,
+	#[serde(default)]
+	is_sufficient: bool,
+	#[serde(default)]
+	holders: Vec<(String, Balance)>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct GenesisCollectionEntry {
+	id: u32,
+	owner: Option<String>,
+	#[serde(default)]
+	metadata: String,
+	#[serde(default)]
+	items: Vec<GenesisItemEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct GenesisItemEntry {
+	id: u32,
+	owner: Option<String>,
+	#[serde(default)]
+	metadata: String,
+}
+
+/// Assets and collections created at genesis.
+#[derive(Clone, Default)]
+struct GenesisTokens {
+	assets: Vec<(AssetId, AccountId, bool, Balance)>,
+	metadata: Vec<(AssetId, Vec<u8>, Vec<u8>, u8)>,
+	accounts: Vec<(AssetId, AccountId, Balance)>,
+	collections: Vec<GenesisCollection<u32, u32, AccountId>>,
+}
+
+/// Reads the assets and collections to create at genesis from the `--genesis-tokens` file.
+fn genesis_tokens(path: &Path) -> Result<GenesisTokens, String> {
+	let data = fs::read(path).map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+	let file: GenesisTokensFile = serde_json::from_slice(&data)
+		.map_err(|e| format!("Invalid genesis tokens {}: {}", path.display(), e))?;
+
+	let mut tokens = GenesisTokens::default();
+	for asset in file.assets {
+		let owner = account_or_alice(asset.owner)?;
+		tokens.assets.push((asset.id, owner, asset.is_sufficient, asset.min_balance));
+		tokens
+			.metadata
+			.push((asset.id, asset.name.into(), asset.symbol.into(), asset.decimals));
+		for (holder, balance) in asset.holders {
+			tokens.accounts.push((asset.id, account_or_alice(Some(holder))?, balance));
+		}
+	}
+	// `pallet_genesis_uniques` panics on collections or items `pallet_uniques` rejects.
+	let string_limit = UniquesStringLimit::get() as usize;
+	let mut collection_ids = BTreeSet::new();
+	for collection in file.collections {
+		let id = collection.id;
+		if !collection_ids.insert(id) {
+			return Err(format!("Collection {} is listed more than once", id))
+		}
+		if collection.metadata.len() > string_limit {
+			return Err(format!(
+				"Metadata of collection {} is longer than {} bytes",
+				id, string_limit
+			))
+		}
+
+		let mut item_ids = BTreeSet::new();
+		let items = collection
+			.items
+			.into_iter()
+			.map(|item| {
+				if !item_ids.insert(item.id) {
+					return Err(format!(
+						"Item {} of collection {} is listed more than once",
+						item.id, id
+					))
+				}
+				if item.metadata.len() > string_limit {
+					return Err(format!(
+						"Metadata of item {} of collection {} is longer than {} bytes",
+						item.id, id, string_limit
+					))
+				}
+				Ok(GenesisItem {
+					id: item.id,
+					owner: account_or_alice(item.owner).map_err(|e| {
+						format!("Owner of item {} of collection {}: {}", item.id, id, e)
+					})?,
+					metadata: item.metadata.into(),
+				})
+			})
+			.collect::<Result<_, String>>()?;
+		tokens.collections.push(GenesisCollection {
+			id,
+			owner: account_or_alice(collection.owner)
+				.map_err(|e| format!("Owner of collection {}: {}", id, e))?,
+			metadata: collection.metadata.into(),
+			items,
+		});
+	}
+
+	Ok(tokens)
+}
+
+/// Proof size limit of a parachain block on Polkadot and Kusama.
+const PARACHAIN_MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
+
+/// Properties of the chain, with the token symbol of the preset unless `--token-symbol` is given.
+fn properties(symbol: Option<&str>, dev: &DevParams) -> Properties {
+	let mut properties = Properties::new();
+	if let Some(symbol) = dev.token_symbol.as_deref().or(symbol) {
+		properties.insert("tokenSymbol".into(), symbol.into());
+	}
+	properties.insert("tokenDecimals".into(), dev.token_decimals.into());
+	properties.insert("ss58Format".into(), dev.ss58_prefix().into());
+	properties
+}
+
+pub fn development_config(dev: &DevParams) -> Result<ChainSpec, String> {
+	let properties = properties(None, dev);
+	chain_spec("Development", "dev", ChainType::Development, properties, true, dev)
+}
+
+/// Like the development chain, but with its own ID and a token symbol.
+pub fn local_testnet_config(dev: &DevParams) -> Result<ChainSpec, String> {
+	let properties = properties(Some("LOCAL"), dev);
+	chain_spec("Local Testnet", "local_testnet", ChainType::Local, properties, true, dev)
+}
+
+/// Like the development chain, but only the sudo key, Alice, is endowed, so that it can pay for
+/// root calls and genesis contracts.
+pub fn empty_config(dev: &DevParams) -> Result<ChainSpec, String> {
+	let properties = properties(None, dev);
+	chain_spec("Empty", "empty", ChainType::Development, properties, false, dev)
+}
+
+/// Genesis of the production chain the runtime profile mirrors: fees are charged, blocks have the
+/// proof size limit of a parachain unless set otherwise and the token is the one of the chain.
+pub fn production_config(dev: &DevParams) -> Result<ChainSpec, String> {
+	let mut dev = dev.clone();
+	dev.free_transactions = false;
+	dev.max_block_pov_size = dev.max_block_pov_size.or(Some(PARACHAIN_MAX_POV_SIZE));
+	let properties = properties(Some(profile::TOKEN_SYMBOL), &dev);
+	chain_spec("Production Profile", "production", ChainType::Live, properties, true, &dev)
+}
+
+/// Builds a chain spec of the development genesis, endowing the dev accounts if `endowed`, or only
+/// the sudo key otherwise.
+fn chain_spec(
+	name: &str,
+	id: &str,
+	chain_type: ChainType,
+	properties: Properties,
+	endowed: bool,
+	dev: &DevParams,
+) -> Result<ChainSpec, String> {
 	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
-	let mut properties = serde_json::map::Map::new();
-	properties.insert("tokenDecimals".into(), 18.into());
+	let dev = dev.clone();
+	let root_key = get_account_id_from_seed::<sr25519::Public>("Alice");
+	let accounts = if endowed {
+		dev.accounts.accounts()?.into_iter().map(|account| account.id).collect()
+	} else {
+		vec![root_key.clone()]
+	};
+	let tokens = match &dev.genesis_tokens {
+		Some(path) => genesis_tokens(path)?,
+		None => GenesisTokens::default(),
+	};
+	let contracts = match &dev.genesis_contracts {
+		Some(path) => {
+			let genesis = testnet_genesis(
+				wasm_binary,
+				root_key.clone(),
+				accounts.clone(),
+				Vec::new(),
+				tokens.clone(),
+				&dev,
+				true,
+			);
+			genesis_contracts(path, genesis)?
+		},
+		None => Vec::new(),
+	};
 
 	Ok(ChainSpec::from_genesis(
 		// Name
-		"Development",
+		name,
 		// ID
-		"dev",
-		ChainType::Development,
+		id,
+		chain_type,
 		move || {
 			testnet_genesis(
 				wasm_binary,
 				// Sudo account
-				get_account_id_from_seed::<sr25519::Public>("Alice"),
+				root_key.clone(),
 				// Pre-funded accounts
-				vec![
-					get_account_id_from_seed::<sr25519::Public>("Alice"),
-					get_account_id_from_seed::<sr25519::Public>("Bob"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie"),
-					get_account_id_from_seed::<sr25519::Public>("Dave"),
-					get_account_id_from_seed::<sr25519::Public>("Eve"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie"),
-					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Charlie//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Dave//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
-					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
-				],
+				accounts.clone(),
+				contracts.clone(),
+				tokens.clone(),
+				&dev,
 				true,
 			)
 		},
@@ -81,6 +336,9 @@ fn testnet_genesis(
 	wasm_binary: &[u8],
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	contracts: Vec<GenesisContract<AccountId, Balance>>,
+	tokens: GenesisTokens,
+	dev: &DevParams,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -89,14 +347,100 @@ fn testnet_genesis(
 			code: wasm_binary.to_vec(),
 		},
 		balances: BalancesConfig {
-			// Configure endowed accounts with initial balance of 1 << 100.
-			balances: endowed_accounts.iter().cloned().map(|k| (k, 1 << 100)).collect(),
+			// Configure endowed accounts with the initial balance of `--dev-balance`.
+			balances: endowed_accounts
+				.iter()
+				.cloned()
+				.map(|k| (k, dev.accounts.dev_balance))
+				.collect(),
 		},
 		sudo: SudoConfig {
 			// Assign network admin rights.
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
-		assets: Default::default(),
+		assets: AssetsConfig {
+			assets: tokens.assets,
+			metadata: tokens.metadata,
+			accounts: tokens.accounts,
+		},
+		asset_rate: Default::default(),
+		free_transactions: FreeTransactionsConfig { enabled: dev.free_transactions },
+		pov_limit: PovLimitConfig {
+			max_block_proof_size: dev.max_block_pov_size,
+			max_extrinsic_proof_size: dev.max_extrinsic_pov_size,
+		},
+		chain_properties: ChainPropertiesConfig { ss58_prefix: dev.ss58_prefix },
+		contracts_call_filter: Default::default(),
+		dev_randomness: Default::default(),
+		dev_oracle: Default::default(),
+		genesis_contracts: GenesisContractsConfig { contracts },
+		genesis_uniques: GenesisUniquesConfig { collections: tokens.collections },
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::path::PathBuf;
+
+	/// Writes `content` to a file in the temporary directory, unique to the test `name`.
+	fn temp_file(name: &str, content: &str) -> PathBuf {
+		let path =
+			std::env::temp_dir().join(format!("swanky-chain-spec-{}-{}", std::process::id(), name));
+		fs::write(&path, content).unwrap();
+		path
+	}
+
+	fn collections_error(name: &str, collections: &str) -> String {
+		let path = temp_file(name, &format!(r#"{{"collections": {}}}"#, collections));
+		let tokens = genesis_tokens(&path);
+		fs::remove_file(&path).unwrap();
+		tokens.err().expect("collections are rejected")
+	}
+
+	#[test]
+	fn invalid_collections_are_rejected() {
+		assert_eq!(
+			collections_error("duplicate-collection", r#"[{"id": 1}, {"id": 1}]"#),
+			"Collection 1 is listed more than once"
+		);
+		assert_eq!(
+			collections_error("duplicate-item", r#"[{"id": 1, "items": [{"id": 2}, {"id": 2}]}]"#),
+			"Item 2 of collection 1 is listed more than once"
+		);
+
+		let limit = UniquesStringLimit::get();
+		let metadata = "a".repeat(limit as usize + 1);
+		assert_eq!(
+			collections_error(
+				"long-metadata",
+				&format!(r#"[{{"id": 1, "metadata": "{}"}}]"#, metadata)
+			),
+			format!("Metadata of collection 1 is longer than {} bytes", limit)
+		);
+		assert_eq!(
+			collections_error(
+				"long-item-metadata",
+				&format!(r#"[{{"id": 1, "items": [{{"id": 2, "metadata": "{}"}}]}}]"#, metadata)
+			),
+			format!("Metadata of item 2 of collection 1 is longer than {} bytes", limit)
+		);
+		assert!(collections_error("invalid-owner", r#"[{"id": 1, "owner": "nobody"}]"#)
+			.starts_with("Owner of collection 1: "));
+	}
+
+	#[test]
+	fn valid_collections_are_read() {
+		let path = temp_file(
+			"valid-collections",
+			r#"{"collections": [{"id": 1, "items": [{"id": 1}, {"id": 2}]}, {"id": 2}]}"#,
+		);
+		let tokens = genesis_tokens(&path);
+		fs::remove_file(&path).unwrap();
+
+		let collections = tokens.expect("collections are valid").collections;
+		assert_eq!(collections.len(), 2);
+		assert_eq!(collections[0].items.len(), 2);
 	}
 }
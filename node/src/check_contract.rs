@@ -0,0 +1,113 @@
+//! Offline validation of contract code, to reject contracts early in CI jobs.
+
+use std::{
+	fs,
+	path::{Path, PathBuf},
+};
+
+use codec::{Compact, Encode, MaxEncodedLen};
+use parity_wasm::elements::{External, Module};
+use sc_cli::{Error, Result};
+use swanky_runtime::{AccountId, Balance};
+
+use crate::lint::{lint, Limits, Target, Warning};
+
+/// The `check-contract` command used to validate contract code without starting a node.
+#[derive(Debug, Clone, clap::Parser)]
+pub struct CheckContractCmd {
+	/// Path of the `.wasm` code or the `.contract` bundle to check.
+	#[clap(value_name = "FILE")]
+	pub path: PathBuf,
+
+	/// Check against the limits of the given production chain instead of this runtime.
+	#[clap(long, value_name = "TARGET", value_enum)]
+	pub target: Option<Target>,
+}
+
+impl CheckContractCmd {
+	/// Print a report of the code, failing if the runtime would reject it.
+	pub fn run(&self) -> Result<()> {
+		let code = read_code(&self.path).map_err(Error::Input)?;
+		let limits = match self.target {
+			Some(target) => Limits::from(target),
+			None => Limits::runtime(),
+		};
+
+		let warnings = lint(&code, &limits);
+
+		println!("Size: {} bytes, at most {} allowed", code.len(), limits.max_code_len);
+		if let Ok(module) = parity_wasm::deserialize_buffer::<Module>(&code) {
+			report_module(&module);
+		}
+		println!("Deterministic: {}", !warnings.contains(&Warning::FloatingPoint));
+		println!("Upload deposit: {} (rough estimate)", upload_deposit(code.len(), &limits));
+
+		if warnings.is_empty() {
+			println!("No problems found");
+			return Ok(())
+		}
+
+		println!("Problems:");
+		for warning in &warnings {
+			println!("  {}", warning);
+		}
+		Err(Error::Input(format!("{} problems found in the contract code", warnings.len())))
+	}
+}
+
+/// Reads the code from a `.wasm` file or from the `source.wasm` field of a `.contract` bundle.
+pub fn read_code(path: &Path) -> std::result::Result<Vec<u8>, String> {
+	let data = fs::read(path).map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+	if path.extension().map_or(true, |extension| extension != "contract") {
+		return Ok(data)
+	}
+
+	let bundle: serde_json::Value = serde_json::from_slice(&data)
+		.map_err(|e| format!("Invalid contract bundle {}: {}", path.display(), e))?;
+	let wasm = bundle["source"]["wasm"]
+		.as_str()
+		.ok_or_else(|| format!("Contract bundle {} has no `source.wasm` field", path.display()))?;
+	sp_core::bytes::from_hex(wasm)
+		.map_err(|e| format!("Invalid `source.wasm` field in {}: {}", path.display(), e))
+}
+
+/// Prints the imports and the memory limits of the module.
+fn report_module(module: &Module) {
+	println!("Imports:");
+	for import in module.import_section().map_or(&[][..], |section| section.entries()) {
+		match import.external() {
+			External::Function(_) => println!("  {}::{}", import.module(), import.field()),
+			External::Memory(memory) => println!(
+				"  {}::{} (memory of {} to {} pages)",
+				import.module(),
+				import.field(),
+				memory.limits().initial(),
+				memory
+					.limits()
+					.maximum()
+					.map_or_else(|| "unlimited".into(), |maximum| maximum.to_string()),
+			),
+			External::Table(_) | External::Global(_) =>
+				println!("  {}::{}", import.module(), import.field()),
+		}
+	}
+}
+
+/// Estimates the deposit of uploading code of `len` bytes, as `pallet_contracts` charges it.
+///
+/// Uploading stores three items: the original code, the instrumented code along with a few fields
+/// of its module, and the owner info of the code. Only the length of the instrumented code is not
+/// known before the runtime instruments it. It is assumed to be `len`, while instrumentation
+/// usually makes code longer, so the deposit is a rough estimate.
+fn upload_deposit(len: usize, limits: &Limits) -> Balance {
+	// The compact instruction weights version, initial and maximum memory pages, and the
+	// determinism of the module, a byte each for usual values.
+	let module = len + Compact(len as u32).encoded_size() + 4;
+	let owner_info = AccountId::max_encoded_len() +
+		Compact::<Balance>::max_encoded_len() +
+		Compact::<u64>::max_encoded_len();
+	let bytes = (module + len + owner_info) as Balance;
+	bytes
+		.saturating_mul(limits.deposit_per_byte)
+		.saturating_add(3 * limits.deposit_per_item)
+}
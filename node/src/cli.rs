@@ -1,12 +1,113 @@
+use crate::{
+	accounts::{AccountsCmd, DevAccountsParams},
+	check_contract::CheckContractCmd,
+	lint::Target,
+};
 use sc_cli::RunCmd;
+use sp_core::Get;
+use std::path::PathBuf;
+use swanky_runtime::SS58Prefix;
 
 #[derive(Debug, clap::Parser)]
+#[clap(after_help = "Unless `--state-pruning` is given, the state of every block is kept, as \
+	with `--state-pruning archive`, so that `dev_reset` can revert finalized blocks.")]
 pub struct Cli {
 	#[clap(subcommand)]
 	pub subcommand: Option<Subcommand>,
 
 	#[clap(flatten)]
 	pub run: RunCmd,
+
+	#[clap(flatten)]
+	pub dev: DevParams,
+}
+
+/// Options of the development chain.
+#[derive(Debug, Clone, clap::Args)]
+pub struct DevParams {
+	#[clap(flatten)]
+	pub accounts: DevAccountsParams,
+
+	/// Make transactions free from genesis on, still reporting the fee they would cost.
+	///
+	/// Only has an effect when the chain is created, e.g. with `--tmp` or after `purge-chain`.
+	#[clap(long)]
+	pub free_transactions: bool,
+
+	/// Limit the proof size (PoV) of blocks to the given number of bytes, like on a parachain.
+	///
+	/// Blocks are built with storage proof recording and stop taking extrinsics once the block
+	/// and its proof would exceed the limit. New chains also get it as the declared proof size
+	/// limit of blocks. Without it, the declared limit of the chain when the node starts is used.
+	#[clap(long, value_name = "BYTES")]
+	pub max_block_pov_size: Option<u64>,
+
+	/// Limit the declared proof size of a single extrinsic to the given number of bytes.
+	///
+	/// Only has an effect when the chain is created, e.g. with `--tmp` or after `purge-chain`.
+	#[clap(long, value_name = "BYTES")]
+	pub max_extrinsic_pov_size: Option<u64>,
+
+	/// Check every uploaded contract code against the limits of the given production chain,
+	/// logging what it would be rejected for.
+	#[clap(long, value_name = "TARGET", value_enum)]
+	pub lint_contracts: Option<Target>,
+
+	/// Instantiate the contracts listed in the given JSON file at genesis.
+	///
+	/// Every entry has the `path` of a `.contract` or `.wasm` file relative to the list, the
+	/// `constructor` selector and optionally the SCALE encoded `args`, the `salt`, the `endowment`
+	/// and the SS58 address of the `deployer`, Alice by default. Only has an effect when the chain
+	/// is created, e.g. with `--tmp` or after `purge-chain`.
+	#[clap(long, value_name = "FILE")]
+	pub genesis_contracts: Option<PathBuf>,
+
+	/// Create the `pallet-assets` assets and `pallet-uniques` collections listed in the given
+	/// JSON file at genesis.
+	///
+	/// Assets have an `id`, `name`, `symbol`, `decimals`, `minBalance` and optionally an `owner`,
+	/// `isSufficient` and `holders` as `[address, balance]` pairs. Collections have an `id` and
+	/// optionally an `owner`, `metadata` and pre-minted `items` with an `id`, `owner` and
+	/// `metadata`. Owners are Alice by default. Only has an effect when the chain is created.
+	#[clap(long, value_name = "FILE")]
+	pub genesis_tokens: Option<PathBuf>,
+
+	/// Symbol of the native token shown by wallets, in place of the one of the preset.
+	#[clap(long, value_name = "SYMBOL")]
+	pub token_symbol: Option<String>,
+
+	/// Number of decimals of the native token shown by wallets.
+	#[clap(long, value_name = "DECIMALS", default_value_t = 18)]
+	pub token_decimals: u8,
+
+	/// SS58 prefix of the addresses of the chain, the one of the runtime profile by default.
+	///
+	/// The runtime reads it from the genesis state, so it only has an effect when the chain is
+	/// created.
+	#[clap(long, value_name = "PREFIX")]
+	pub ss58_prefix: Option<u16>,
+
+	/// Seed the genesis with the state of a snapshot, produced by `export-state` or
+	/// `try-runtime`.
+	///
+	/// The runtime code is replaced with the one of this node, sudo is re-keyed to Alice and the
+	/// development accounts are topped up. Only has an effect when the chain is created.
+	#[clap(long, value_name = "SNAPSHOT")]
+	pub fork_from: Option<PathBuf>,
+
+	/// Start from the exact state of a snapshot written by `dev_exportSnapshot`.
+	///
+	/// Unlike with `--fork-from`, the state is loaded as is. Only has an effect when the chain is
+	/// created.
+	#[clap(long, value_name = "FILE", conflicts_with = "fork_from")]
+	pub load_snapshot: Option<PathBuf>,
+}
+
+impl DevParams {
+	/// SS58 prefix of the addresses of the chain.
+	pub fn ss58_prefix(&self) -> u16 {
+		self.ss58_prefix.unwrap_or_else(<SS58Prefix as Get<u16>>::get)
+	}
 }
 
 #[derive(Debug, clap::Subcommand)]
@@ -36,6 +137,12 @@ pub enum Subcommand {
 	/// Revert the chain to a previous state.
 	Revert(sc_cli::RevertCmd),
 
+	/// Check contract code against the limits of the runtime, without starting a node.
+	CheckContract(CheckContractCmd),
+
+	/// Print the endowed accounts of the development chain.
+	Accounts(AccountsCmd),
+
 	/// Sub-commands concerned with benchmarking.
 	#[cfg(feature = "frame-benchmarking")]
 	#[clap(subcommand)]
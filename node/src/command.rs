@@ -1,10 +1,11 @@
 use crate::{
 	chain_spec,
 	cli::{Cli, Subcommand},
-	service,
+	fork, service, snapshot,
 };
-use sc_cli::{ChainSpec, RuntimeVersion, SubstrateCli};
+use sc_cli::{ChainSpec, DatabasePruningMode, RuntimeVersion, SubstrateCli};
 use sc_service::PartialComponents;
+use std::path::{Path, PathBuf};
 
 impl SubstrateCli for Cli {
 	fn impl_name() -> String {
@@ -32,10 +33,26 @@ impl SubstrateCli for Cli {
 	}
 
 	fn load_spec(&self, id: &str) -> Result<Box<dyn sc_service::ChainSpec>, String> {
-		Ok(match id {
-			"dev" => Box::new(chain_spec::development_config()?),
-			_ => Box::new(chain_spec::development_config()?),
-		})
+		let mut spec: Box<dyn sc_service::ChainSpec> = match id {
+			"dev" | "" => Box::new(chain_spec::development_config(&self.dev)?),
+			"local" => Box::new(chain_spec::local_testnet_config(&self.dev)?),
+			"empty" => Box::new(chain_spec::empty_config(&self.dev)?),
+			"production" => Box::new(chain_spec::production_config(&self.dev)?),
+			path if Path::new(path).is_file() =>
+				Box::new(chain_spec::ChainSpec::from_json_file(PathBuf::from(path))?),
+			id =>
+				return Err(format!(
+					"Unknown chain `{}`, expected `dev`, `local`, `empty`, `production` or the \
+					 path of a chain spec file",
+					id
+				)),
+		};
+		match (&self.dev.load_snapshot, &self.dev.fork_from) {
+			(Some(path), _) => snapshot::load(spec.as_mut(), path)?,
+			(None, Some(path)) => fork::fork(spec.as_mut(), path, &self.dev)?,
+			(None, None) => (),
+		}
+		Ok(spec)
 	}
 
 	fn native_runtime_version(_: &Box<dyn ChainSpec>) -> &'static RuntimeVersion {
@@ -95,6 +112,8 @@ pub fn run() -> sc_cli::Result<()> {
 				Ok((cmd.run(client, backend, None), task_manager))
 			})
 		},
+		Some(Subcommand::CheckContract(cmd)) => cmd.run(),
+		Some(Subcommand::Accounts(cmd)) => cmd.run(),
 		#[cfg(feature = "frame-benchmarking")]
 		Some(Subcommand::Benchmark(cmd)) => {
 			let runner = cli.create_runner(cmd)?;
@@ -154,9 +173,18 @@ pub fn run() -> sc_cli::Result<()> {
 				You can enable it with `--features try-runtime`."
 			.into()),
 		None => {
-			let runner = cli.create_runner(&cli.run)?;
+			// `dev_reset` reverts finalized blocks, which needs the state of every block.
+			let mut run = cli.run.clone();
+			let state_pruning = &mut run.import_params.pruning_params.state_pruning;
+			let archive = state_pruning.is_none();
+			state_pruning.get_or_insert(DatabasePruningMode::Archive);
+			let runner = cli.create_runner(&run)?;
+			if archive {
+				log::info!("Keeping the state of every block, pass `--state-pruning` to change it");
+			}
+			let dev = cli.dev.clone();
 			runner.run_node_until_exit(|config| async move {
-				service::new_full(config).map_err(sc_cli::Error::Service)
+				service::new_full(config, dev).map_err(sc_cli::Error::Service)
 			})
 		},
 	}
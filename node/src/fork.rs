@@ -0,0 +1,104 @@
+//! Forking the development chain from an offline state snapshot.
+//!
+//! `--fork-from` seeds the genesis of the chain with the state of a snapshot, either a chain spec
+//! with raw genesis as produced by `export-state`, or a snapshot of `try-runtime`. The state is
+//! made usable locally: the runtime code is replaced with the one of this node, sudo is re-keyed
+//! to Alice and the development accounts are topped up to `--dev-balance`.
+//!
+//! The runtime of this node has to understand the state of the snapshot, so pallets shared with
+//! the production chain, e.g. contracts and assets, are available while others are ignored.
+
+use std::{fs, path::Path};
+
+use codec::{Decode, Encode};
+use sc_service::ChainSpec;
+use sp_core::{
+	hashing::{blake2_128, twox_128},
+	sr25519,
+	storage::{well_known_keys, ChildInfo, StateVersion, Storage, StorageChild},
+};
+use sp_runtime::BuildStorage;
+use swanky_runtime::{AccountId, Balance, Hash, Index, WASM_BINARY};
+
+use crate::{chain_spec::get_account_id_from_seed, cli::DevParams};
+
+type AccountInfo = frame_system::AccountInfo<Index, pallet_balances::AccountData<Balance>>;
+
+/// Key value pairs of a storage trie.
+type KeyValues = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// Snapshot as written by `try-runtime`.
+#[derive(Decode)]
+struct Snapshot {
+	_state_version: StateVersion,
+	_block_hash: Hash,
+	top: KeyValues,
+	child: Vec<(ChildInfo, KeyValues)>,
+}
+
+/// Replaces the genesis of `spec` with the state of the snapshot at `path`.
+pub fn fork(spec: &mut dyn ChainSpec, path: &Path, dev: &DevParams) -> Result<(), String> {
+	let mut storage = load(path)?;
+	let wasm_binary = WASM_BINARY.ok_or_else(|| "Development wasm not available".to_string())?;
+	storage.top.insert(well_known_keys::CODE.to_vec(), wasm_binary.to_vec());
+
+	let alice = get_account_id_from_seed::<sr25519::Public>("Alice");
+	storage.top.insert(storage_key(b"Sudo", b"Key"), alice.encode());
+
+	let accounts = dev.accounts.accounts()?;
+	let mut issued: Balance = 0;
+	for account in accounts {
+		let key = account_key(&account.id);
+		let mut info = match storage.top.get(&key) {
+			Some(data) => AccountInfo::decode(&mut &data[..])
+				.map_err(|e| format!("Invalid account {} in snapshot: {}", account.id, e))?,
+			None => AccountInfo { providers: 1, ..Default::default() },
+		};
+		issued = issued.saturating_add(dev.accounts.dev_balance.saturating_sub(info.data.free));
+		info.data.free = info.data.free.max(dev.accounts.dev_balance);
+		storage.top.insert(key, info.encode());
+	}
+
+	let issuance_key = storage_key(b"Balances", b"TotalIssuance");
+	let issuance = storage
+		.top
+		.get(&issuance_key)
+		.and_then(|data| Balance::decode(&mut &data[..]).ok())
+		.unwrap_or_default();
+	storage.top.insert(issuance_key, issuance.saturating_add(issued).encode());
+
+	spec.set_storage(storage);
+	Ok(())
+}
+
+/// Loads the state of an `export-state` chain spec or a `try-runtime` snapshot.
+fn load(path: &Path) -> Result<Storage, String> {
+	let data = fs::read(path).map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+	if data.first() == Some(&b'{') {
+		let spec = crate::chain_spec::ChainSpec::from_json_bytes(data)?;
+		return spec.build_storage()
+	}
+
+	let snapshot = Snapshot::decode(&mut &data[..])
+		.map_err(|e| format!("Invalid snapshot {}: {}", path.display(), e))?;
+	let children_default = snapshot
+		.child
+		.into_iter()
+		.map(|(child_info, data)| {
+			let key = child_info.storage_key().to_vec();
+			(key, StorageChild { data: data.into_iter().collect(), child_info })
+		})
+		.collect();
+
+	Ok(Storage { top: snapshot.top.into_iter().collect(), children_default })
+}
+
+fn storage_key(pallet: &[u8], item: &[u8]) -> Vec<u8> {
+	[twox_128(pallet), twox_128(item)].concat()
+}
+
+/// Key of the `System::Account` entry of `account`.
+fn account_key(account: &AccountId) -> Vec<u8> {
+	let encoded = account.encode();
+	[&storage_key(b"System", b"Account")[..], &blake2_128(&encoded), &encoded].concat()
+}
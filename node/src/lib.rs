@@ -1,3 +1,9 @@
+pub mod accounts;
 pub mod chain_spec;
+pub mod check_contract;
+pub mod cli;
+pub mod fork;
+pub mod lint;
 pub mod rpc;
 pub mod service;
+pub mod snapshot;
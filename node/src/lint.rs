@@ -0,0 +1,716 @@
+//! Production compatibility lint of contract code.
+//!
+//! The node accepts contracts that production chains reject: it exposes the unstable host
+//! functions, allows larger code and implements chain extensions the target chain may lack. The
+//! lint reports such problems ahead of deployment, for a given [`Target`] chain. It is available
+//! through the `contracts_lint` RPC, and runs with `--lint-contracts` on every uploaded code and on
+//! the code of `ContractsApi_upload_code` dry-runs through `pov_call`. The [`Linter`] keeps the
+//! warnings by code hash, for clients to read through `contracts_lintWarnings`.
+//!
+//! The same checks against the limits of this runtime back the `check-contract` subcommand.
+
+use std::{
+	collections::HashMap,
+	fmt,
+	sync::{Arc, Mutex},
+};
+
+use codec::{Decode, Encode};
+use futures::StreamExt;
+use parity_wasm::elements::{BlockType, External, Instruction, Internal, Module, Type, ValueType};
+use sc_client_api::{BlockchainEvents, StorageProvider};
+use serde::Deserialize;
+use sp_core::{
+	hashing::{blake2_256, twox_128},
+	storage::StorageKey,
+	Get,
+};
+use swanky_runtime::{
+	chain_extensions, profile, Balance, DepositPerByte, DepositPerItem, Hash, Runtime, RuntimeEvent,
+};
+
+use crate::service::FullClient;
+
+/// Log target of the lint.
+const LOG_TARGET: &str = "contracts-lint";
+
+/// Functions of the `seal0` module which are part of the unstable interface.
+const UNSTABLE_FUNCTIONS: &[&str] = &[
+	"call_runtime",
+	"take_storage",
+	"reentrance_count",
+	"account_reentrance_count",
+	"instantiation_nonce",
+];
+
+/// Chain the lint checks contracts against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Target {
+	/// Astar mainnet.
+	Astar,
+	/// Shiden, the canary network on Kusama.
+	Shiden,
+	/// Shibuya testnet.
+	Shibuya,
+}
+
+impl Target {
+	/// Maximum length of contract code.
+	fn max_code_len(self) -> usize {
+		let max_code_len = match self {
+			Target::Astar => profile::astar::MAX_CODE_LEN,
+			Target::Shiden => profile::shiden::MAX_CODE_LEN,
+			Target::Shibuya => profile::shibuya::MAX_CODE_LEN,
+		};
+		max_code_len as usize
+	}
+
+	/// Whether the unstable host functions are available.
+	fn unstable_interface(self) -> bool {
+		match self {
+			Target::Astar => profile::astar::UNSAFE_UNSTABLE_INTERFACE,
+			Target::Shiden => profile::shiden::UNSAFE_UNSTABLE_INTERFACE,
+			Target::Shibuya => profile::shibuya::UNSAFE_UNSTABLE_INTERFACE,
+		}
+	}
+
+	/// Storage deposit of `items` items and `bytes` bytes stored by contracts.
+	fn contracts_deposit(self, items: u32, bytes: u32) -> Balance {
+		match self {
+			Target::Astar => profile::astar::contracts_deposit(items, bytes),
+			Target::Shiden => profile::shiden::contracts_deposit(items, bytes),
+			Target::Shibuya => profile::shibuya::contracts_deposit(items, bytes),
+		}
+	}
+
+	/// IDs of the chain extensions available to contracts.
+	fn chain_extensions(self) -> &'static [u16] {
+		match self {
+			Target::Astar => &[0x0000],
+			Target::Shiden => &[0x0000, 0x0002],
+			Target::Shibuya => &[0x0000, 0x0001, 0x0002],
+		}
+	}
+}
+
+impl fmt::Display for Target {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Target::Astar => write!(f, "Astar"),
+			Target::Shiden => write!(f, "Shiden"),
+			Target::Shibuya => write!(f, "Shibuya"),
+		}
+	}
+}
+
+/// Limits contract code is checked against.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Limits {
+	/// Maximum length of contract code.
+	pub max_code_len: usize,
+	/// Whether the unstable host functions are available.
+	pub unstable_interface: bool,
+	/// IDs of the chain extensions available to contracts.
+	pub chain_extensions: Vec<u16>,
+	/// Limits of the contracts `Schedule`.
+	pub schedule: pallet_contracts::Limits,
+	/// Storage deposit per item stored by contracts.
+	pub deposit_per_item: Balance,
+	/// Storage deposit per byte stored by contracts.
+	pub deposit_per_byte: Balance,
+}
+
+impl Limits {
+	/// Limits of this runtime.
+	pub fn runtime() -> Self {
+		Limits {
+			max_code_len: profile::MAX_CODE_LEN as usize,
+			unstable_interface: profile::UNSAFE_UNSTABLE_INTERFACE,
+			chain_extensions: chain_extensions::registered().iter().map(|info| info.id).collect(),
+			schedule: swanky_runtime::Schedule::get().limits,
+			deposit_per_item: DepositPerItem::get(),
+			deposit_per_byte: DepositPerByte::get(),
+		}
+	}
+}
+
+impl From<Target> for Limits {
+	fn from(target: Target) -> Self {
+		Limits {
+			max_code_len: target.max_code_len(),
+			unstable_interface: target.unstable_interface(),
+			chain_extensions: target.chain_extensions().to_vec(),
+			schedule: pallet_contracts::Schedule::<Runtime>::default().limits,
+			deposit_per_item: target.contracts_deposit(1, 0),
+			deposit_per_byte: target.contracts_deposit(0, 1),
+		}
+	}
+}
+
+/// A problem the target chain would reject the contract for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Warning {
+	/// The code is not a valid Wasm module.
+	InvalidModule(String),
+	/// The code is longer than the target allows.
+	CodeTooLong { len: usize, max: usize },
+	/// A host function of the unstable interface is imported.
+	UnstableFunction { module: String, name: String },
+	/// A chain extension the target lacks is called.
+	MissingChainExtension { id: u32 },
+	/// A chain extension is called with an ID that is not a constant, so it cannot be checked.
+	UnknownChainExtension,
+	/// Floating point types or instructions are used.
+	FloatingPoint,
+	/// The memory is not imported with a maximum within the limit of the schedule.
+	InvalidMemory(String),
+	/// Something other than the `call` and `deploy` functions is exported.
+	UnexpectedExport(String),
+	/// A limit of the schedule is exceeded.
+	ExceedsLimit { what: &'static str, len: u32, max: u32 },
+}
+
+impl fmt::Display for Warning {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Warning::InvalidModule(e) => write!(f, "invalid Wasm module: {}", e),
+			Warning::CodeTooLong { len, max } =>
+				write!(f, "code is {} bytes long, more than the {} bytes allowed", len, max),
+			Warning::UnstableFunction { module, name } =>
+				write!(f, "`{}::{}` is part of the unstable interface", module, name),
+			Warning::MissingChainExtension { id } => write!(
+				f,
+				"chain extension 0x{:04x} of function 0x{:08x} is not available",
+				id >> 16,
+				id
+			),
+			Warning::UnknownChainExtension =>
+				write!(f, "chain extension is called with an ID that cannot be checked"),
+			Warning::FloatingPoint => write!(f, "floating point types or instructions are used"),
+			Warning::InvalidMemory(e) => write!(f, "invalid memory: {}", e),
+			Warning::UnexpectedExport(name) => write!(f, "unexpected export `{}`", name),
+			Warning::ExceedsLimit { what, len, max } =>
+				write!(f, "{} {} exceed the limit of {}", len, what, max),
+		}
+	}
+}
+
+/// Returns the problems a chain with the given `limits` would reject `code` for.
+pub fn lint(code: &[u8], limits: &Limits) -> Vec<Warning> {
+	let mut warnings = Vec::new();
+
+	if code.len() > limits.max_code_len {
+		warnings.push(Warning::CodeTooLong { len: code.len(), max: limits.max_code_len });
+	}
+
+	let module = match parity_wasm::deserialize_buffer::<Module>(code) {
+		Ok(module) => module,
+		Err(e) => {
+			warnings.push(Warning::InvalidModule(e.to_string()));
+			return warnings
+		},
+	};
+
+	lint_imports(&module, limits, &mut warnings);
+	lint_chain_extensions(&module, limits, &mut warnings);
+	lint_floats(&module, &mut warnings);
+	lint_schedule(&module, &limits.schedule, &mut warnings);
+
+	if module.memory_section().map_or(false, |section| !section.entries().is_empty()) {
+		warnings.push(Warning::InvalidMemory("memory is defined instead of imported".into()));
+	}
+	for export in module.export_section().map_or(&[][..], |section| section.entries()) {
+		let expected = matches!(export.internal(), Internal::Function(_)) &&
+			matches!(export.field(), "call" | "deploy");
+		if !expected {
+			warnings.push(Warning::UnexpectedExport(export.field().into()));
+		}
+	}
+
+	warnings
+}
+
+fn lint_imports(module: &Module, limits: &Limits, warnings: &mut Vec<Warning>) {
+	for import in module.import_section().map_or(&[][..], |section| section.entries()) {
+		match import.external() {
+			External::Function(_) => {
+				let name = import.field().strip_prefix("seal_").unwrap_or(import.field());
+				let unstable = import.module() == "__unstable__" ||
+					(import.module() == "seal0" && UNSTABLE_FUNCTIONS.contains(&name));
+				if unstable && !limits.unstable_interface {
+					warnings.push(Warning::UnstableFunction {
+						module: import.module().into(),
+						name: import.field().into(),
+					});
+				}
+			},
+			External::Memory(memory) => match memory.limits().maximum() {
+				Some(maximum) if maximum <= limits.schedule.memory_pages => (),
+				Some(maximum) => warnings.push(Warning::InvalidMemory(format!(
+					"maximum of {} pages exceeds the limit of {}",
+					maximum, limits.schedule.memory_pages
+				))),
+				None => warnings.push(Warning::InvalidMemory("no maximum is set".into())),
+			},
+			External::Table(_) | External::Global(_) => (),
+		}
+	}
+}
+
+/// Checks the chain extension IDs passed as constants to `seal_call_chain_extension`.
+fn lint_chain_extensions(module: &Module, limits: &Limits, warnings: &mut Vec<Warning>) {
+	let call_chain_extension = module
+		.import_section()
+		.map_or(&[][..], |section| section.entries())
+		.iter()
+		.filter(|import| matches!(import.external(), External::Function(_)))
+		.position(|import| {
+			matches!(import.field(), "seal_call_chain_extension" | "call_chain_extension")
+		});
+	let call_chain_extension = match call_chain_extension {
+		Some(index) => index as u32,
+		None => return,
+	};
+
+	let bodies = module.code_section().map_or(&[][..], |section| section.bodies());
+	for body in bodies {
+		let instructions = body.code().elements();
+		for (i, instruction) in instructions.iter().enumerate() {
+			if instruction != &Instruction::Call(call_chain_extension) {
+				continue
+			}
+
+			// The ID is the first of five arguments, which are usually pushed one by one.
+			let id = i.checked_sub(5).and_then(|start| {
+				let arguments = &instructions[start..i];
+				let pushes = arguments.iter().all(|instruction| {
+					matches!(
+						instruction,
+						Instruction::I32Const(_) |
+							Instruction::GetLocal(_) | Instruction::GetGlobal(_)
+					)
+				});
+				match arguments[0] {
+					Instruction::I32Const(id) if pushes => Some(id as u32),
+					_ => None,
+				}
+			});
+
+			let warning = match id {
+				Some(id) if limits.chain_extensions.contains(&((id >> 16) as u16)) => continue,
+				Some(id) => Warning::MissingChainExtension { id },
+				None => Warning::UnknownChainExtension,
+			};
+			if !warnings.contains(&warning) {
+				warnings.push(warning);
+			}
+		}
+	}
+}
+
+fn lint_floats(module: &Module, warnings: &mut Vec<Warning>) {
+	let types = module.type_section().map_or(&[][..], |section| section.types()).iter().any(
+		|Type::Function(function)| {
+			function.params().iter().any(is_float) || function.results().iter().any(is_float)
+		},
+	);
+	let globals = module
+		.global_section()
+		.map_or(&[][..], |section| section.entries())
+		.iter()
+		.any(|global| is_float(&global.global_type().content_type()));
+	let bodies =
+		module
+			.code_section()
+			.map_or(&[][..], |section| section.bodies())
+			.iter()
+			.any(|body| {
+				body.locals().iter().any(|local| is_float(&local.value_type())) ||
+					body.code().elements().iter().any(is_float_instruction)
+			});
+
+	if types || globals || bodies {
+		warnings.push(Warning::FloatingPoint);
+	}
+}
+
+fn is_float(value_type: &ValueType) -> bool {
+	matches!(value_type, ValueType::F32 | ValueType::F64)
+}
+
+/// Whether `instruction` takes, produces or converts floating point values.
+fn is_float_instruction(instruction: &Instruction) -> bool {
+	use Instruction::*;
+
+	match instruction {
+		Block(BlockType::Value(value_type)) |
+		Loop(BlockType::Value(value_type)) |
+		If(BlockType::Value(value_type)) => is_float(value_type),
+		F32Load(..) | F64Load(..) | F32Store(..) | F64Store(..) | F32Const(_) | F64Const(_) => true,
+		F32Eq | F32Ne | F32Lt | F32Gt | F32Le | F32Ge => true,
+		F64Eq | F64Ne | F64Lt | F64Gt | F64Le | F64Ge => true,
+		F32Abs | F32Neg | F32Ceil | F32Floor | F32Trunc | F32Nearest | F32Sqrt => true,
+		F32Add | F32Sub | F32Mul | F32Div | F32Min | F32Max | F32Copysign => true,
+		F64Abs | F64Neg | F64Ceil | F64Floor | F64Trunc | F64Nearest | F64Sqrt => true,
+		F64Add | F64Sub | F64Mul | F64Div | F64Min | F64Max | F64Copysign => true,
+		I32TruncSF32 | I32TruncUF32 | I32TruncSF64 | I32TruncUF64 => true,
+		I64TruncSF32 | I64TruncUF32 | I64TruncSF64 | I64TruncUF64 => true,
+		F32ConvertSI32 | F32ConvertUI32 | F32ConvertSI64 | F32ConvertUI64 | F32DemoteF64 => true,
+		F64ConvertSI32 | F64ConvertUI32 | F64ConvertSI64 | F64ConvertUI64 | F64PromoteF32 => true,
+		I32ReinterpretF32 | I64ReinterpretF64 | F32ReinterpretI32 | F64ReinterpretI64 => true,
+		_ => false,
+	}
+}
+
+/// Checks the sizes the `Schedule` limits.
+fn lint_schedule(
+	module: &Module,
+	schedule: &pallet_contracts::Limits,
+	warnings: &mut Vec<Warning>,
+) {
+	let mut check = |what, len: usize, max| {
+		if len > max as usize {
+			warnings.push(Warning::ExceedsLimit { what, len: len as u32, max });
+		}
+	};
+
+	let globals = module.global_section().map_or(0, |section| section.entries().len());
+	check("globals", globals, schedule.globals);
+
+	let types = module.type_section().map_or(&[][..], |section| section.types());
+	let parameters = types
+		.iter()
+		.map(|Type::Function(function)| function.params().len())
+		.max()
+		.unwrap_or_default();
+	check("function parameters", parameters, schedule.parameters);
+
+	let table_size = module
+		.table_section()
+		.map_or(&[][..], |section| section.entries())
+		.iter()
+		.map(|table| table.limits().initial() as usize)
+		.max()
+		.unwrap_or_default();
+	check("table elements", table_size, schedule.table_size);
+
+	let br_table_size = module
+		.code_section()
+		.map_or(&[][..], |section| section.bodies())
+		.iter()
+		.flat_map(|body| body.code().elements())
+		.filter_map(|instruction| match instruction {
+			Instruction::BrTable(data) => Some(data.table.len()),
+			_ => None,
+		})
+		.max()
+		.unwrap_or_default();
+	check("`br_table` entries", br_table_size, schedule.br_table_size);
+}
+
+/// Lints code against a target chain, keeping the warnings by code hash.
+///
+/// Clones share the warnings.
+#[derive(Clone)]
+pub struct Linter {
+	target: Target,
+	limits: Limits,
+	warnings: Arc<Mutex<HashMap<Hash, Vec<String>>>>,
+}
+
+impl Linter {
+	/// Create new `Linter` checking code against `target`.
+	pub fn new(target: Target) -> Self {
+		Linter { target, limits: target.into(), warnings: Default::default() }
+	}
+
+	/// Lints `code`, logging and keeping the warnings under its hash, and returns them.
+	pub fn lint(&self, code: &[u8]) -> Vec<String> {
+		let code_hash = Hash::from(blake2_256(code));
+		let warnings = lint(code, &self.limits).iter().map(ToString::to_string).collect::<Vec<_>>();
+		for warning in &warnings {
+			log::warn!(
+				target: LOG_TARGET,
+				"Code {:?} is incompatible with {}: {}",
+				code_hash,
+				self.target,
+				warning
+			);
+		}
+
+		self.warnings
+			.lock()
+			.expect("lock is not poisoned; qed")
+			.insert(code_hash, warnings.clone());
+		warnings
+	}
+
+	/// Returns the warnings of the code with `code_hash`, if it was linted.
+	pub fn warnings(&self, code_hash: &Hash) -> Option<Vec<String>> {
+		self.warnings.lock().expect("lock is not poisoned; qed").get(code_hash).cloned()
+	}
+}
+
+/// Lints the code stored by every imported block.
+pub async fn lint_stored_code(client: Arc<FullClient>, linter: Linter) {
+	let events_key = StorageKey([twox_128(b"System"), twox_128(b"Events")].concat());
+	let mut imported = client.import_notification_stream();
+
+	while let Some(notification) = imported.next().await {
+		let events = client
+			.storage(notification.hash, &events_key)
+			.ok()
+			.flatten()
+			.and_then(|data| {
+				Vec::<frame_system::EventRecord<RuntimeEvent, Hash>>::decode(&mut &data.0[..]).ok()
+			})
+			.unwrap_or_default();
+
+		for record in events {
+			let code_hash = match record.event {
+				RuntimeEvent::Contracts(pallet_contracts::Event::CodeStored { code_hash }) =>
+					code_hash,
+				_ => continue,
+			};
+
+			let code_key = StorageKey(
+				[&twox_128(b"Contracts")[..], &twox_128(b"PristineCode")[..], &code_hash.encode()]
+					.concat(),
+			);
+			let code = client
+				.storage(notification.hash, &code_key)
+				.ok()
+				.flatten()
+				.and_then(|data| Vec::<u8>::decode(&mut &data.0[..]).ok());
+
+			match code {
+				Some(code) => {
+					linter.lint(&code);
+				},
+				None => log::error!(target: LOG_TARGET, "Code {:?} could not be read", code_hash),
+			}
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use parity_wasm::elements::{
+		CodeSection, Func, FuncBody, FunctionSection, FunctionType, ImportEntry, ImportSection,
+		Instructions, Section, TypeSection,
+	};
+
+	/// Returns a module importing the functions `imports` of `seal0`, with a function running
+	/// `instructions`.
+	fn module(imports: &[&str], instructions: Vec<Instruction>) -> Module {
+		let signature = FunctionType::new(vec![ValueType::I32; 5], vec![ValueType::I32]);
+		let imports = imports
+			.iter()
+			.map(|name| ImportEntry::new("seal0".into(), name.to_string(), External::Function(0)))
+			.collect();
+
+		Module::new(vec![
+			Section::Type(TypeSection::with_types(vec![Type::Function(signature)])),
+			Section::Import(ImportSection::with_entries(imports)),
+			Section::Function(FunctionSection::with_entries(vec![Func::new(0)])),
+			Section::Code(CodeSection::with_bodies(vec![FuncBody::new(
+				vec![],
+				Instructions::new(instructions),
+			)])),
+		])
+	}
+
+	/// Returns the instructions calling function `index` with the chain extension ID `id`.
+	fn call(index: u32, id: Instruction) -> Vec<Instruction> {
+		vec![
+			id,
+			Instruction::GetLocal(0),
+			Instruction::I32Const(0),
+			Instruction::GetLocal(1),
+			Instruction::I32Const(0),
+			Instruction::Call(index),
+			Instruction::Drop,
+		]
+	}
+
+	/// Returns a module importing the function `name` of `module`.
+	fn import(module: &str, name: &str) -> Module {
+		let entry = ImportEntry::new(module.into(), name.into(), External::Function(0));
+		Module::new(vec![Section::Import(ImportSection::with_entries(vec![entry]))])
+	}
+
+	fn import_warnings(module: &Module, target: Target) -> Vec<Warning> {
+		let mut warnings = Vec::new();
+		lint_imports(module, &target.into(), &mut warnings);
+		warnings
+	}
+
+	fn float_warnings(module: &Module) -> Vec<Warning> {
+		let mut warnings = Vec::new();
+		lint_floats(module, &mut warnings);
+		warnings
+	}
+
+	fn chain_extension_warnings(module: &Module, target: Target) -> Vec<Warning> {
+		let mut warnings = Vec::new();
+		lint_chain_extensions(module, &target.into(), &mut warnings);
+		warnings
+	}
+
+	#[test]
+	fn available_chain_extension_is_accepted() {
+		let module =
+			module(&["seal_call_chain_extension"], call(0, Instruction::I32Const(0x0000_0001)));
+		assert_eq!(chain_extension_warnings(&module, Target::Astar), vec![]);
+	}
+
+	#[test]
+	fn missing_chain_extension_is_reported() {
+		let module =
+			module(&["seal_call_chain_extension"], call(0, Instruction::I32Const(0x0001_0002)));
+		assert_eq!(
+			chain_extension_warnings(&module, Target::Astar),
+			vec![Warning::MissingChainExtension { id: 0x0001_0002 }]
+		);
+		assert_eq!(chain_extension_warnings(&module, Target::Shibuya), vec![]);
+	}
+
+	#[test]
+	fn chain_extension_call_is_found_among_imports() {
+		let module = module(
+			&["seal_input", "call_chain_extension"],
+			call(1, Instruction::I32Const(0x0002_0000)),
+		);
+		assert_eq!(
+			chain_extension_warnings(&module, Target::Astar),
+			vec![Warning::MissingChainExtension { id: 0x0002_0000 }]
+		);
+	}
+
+	#[test]
+	fn computed_chain_extension_id_is_unknown() {
+		let module = module(&["seal_call_chain_extension"], call(0, Instruction::GetLocal(2)));
+		assert_eq!(
+			chain_extension_warnings(&module, Target::Shibuya),
+			vec![Warning::UnknownChainExtension]
+		);
+
+		let mut instructions = vec![Instruction::I32Const(0x0001_0000), Instruction::I32Add];
+		instructions.extend(call(0, Instruction::GetLocal(2)).into_iter().skip(1));
+		let module = module(&["seal_call_chain_extension"], instructions);
+		assert_eq!(
+			chain_extension_warnings(&module, Target::Shibuya),
+			vec![Warning::UnknownChainExtension]
+		);
+	}
+
+	#[test]
+	fn chain_extension_is_reported_once() {
+		let mut instructions = call(0, Instruction::I32Const(0x0001_0000));
+		instructions.extend(call(0, Instruction::I32Const(0x0001_0000)));
+		let module = module(&["seal_call_chain_extension"], instructions);
+		assert_eq!(
+			chain_extension_warnings(&module, Target::Astar),
+			vec![Warning::MissingChainExtension { id: 0x0001_0000 }]
+		);
+	}
+
+	#[test]
+	fn calls_without_chain_extension_import_are_ignored() {
+		let module = module(&["seal_input"], call(0, Instruction::I32Const(0x0001_0000)));
+		assert_eq!(chain_extension_warnings(&module, Target::Astar), vec![]);
+	}
+
+	#[test]
+	fn unstable_functions_are_reported() {
+		let unstable = [
+			("seal0", "seal_call_runtime"),
+			("seal0", "take_storage"),
+			("__unstable__", "seal_get_storage"),
+		];
+		for (module, name) in unstable {
+			let module_with_import = import(module, name);
+			assert_eq!(
+				import_warnings(&module_with_import, Target::Astar),
+				vec![Warning::UnstableFunction { module: module.into(), name: name.into() }]
+			);
+			assert_eq!(import_warnings(&module_with_import, Target::Shibuya), vec![]);
+		}
+
+		assert_eq!(import_warnings(&import("seal0", "seal_input"), Target::Astar), vec![]);
+	}
+
+	#[test]
+	fn oversize_code_is_reported() {
+		let code = parity_wasm::serialize(module(&[], vec![Instruction::End])).unwrap();
+		let too_long = |warning: &Warning| matches!(warning, Warning::CodeTooLong { .. });
+
+		let limits = Limits { max_code_len: code.len(), ..Limits::from(Target::Astar) };
+		assert!(!lint(&code, &limits).iter().any(too_long));
+
+		let limits = Limits { max_code_len: code.len() - 1, ..Limits::from(Target::Astar) };
+		assert!(lint(&code, &limits)
+			.contains(&Warning::CodeTooLong { len: code.len(), max: code.len() - 1 }));
+	}
+
+	#[test]
+	fn code_length_limit_of_target_is_used() {
+		let max = profile::astar::MAX_CODE_LEN as usize;
+		let code = vec![0; max + 1];
+		assert!(lint(&code, &Target::Astar.into())
+			.contains(&Warning::CodeTooLong { len: max + 1, max }));
+	}
+
+	#[test]
+	fn float_instructions_are_reported() {
+		let instructions = [
+			Instruction::F64Const(0),
+			Instruction::F32Add,
+			Instruction::I32ReinterpretF32,
+			Instruction::Block(BlockType::Value(ValueType::F32)),
+		];
+		for instruction in instructions {
+			let module = module(&[], vec![instruction, Instruction::End]);
+			assert_eq!(float_warnings(&module), vec![Warning::FloatingPoint]);
+		}
+	}
+
+	#[test]
+	fn float_signatures_are_reported() {
+		let signature = FunctionType::new(vec![ValueType::F64], vec![]);
+		let types = TypeSection::with_types(vec![Type::Function(signature)]);
+		let module = Module::new(vec![Section::Type(types)]);
+		assert_eq!(float_warnings(&module), vec![Warning::FloatingPoint]);
+	}
+
+	#[test]
+	fn integer_instructions_are_not_floats() {
+		let module = module(
+			&["seal_input"],
+			vec![
+				Instruction::I64Const(0),
+				Instruction::I32WrapI64,
+				Instruction::Block(BlockType::Value(ValueType::I32)),
+				Instruction::I32Const(0),
+				Instruction::End,
+				Instruction::I32Add,
+				Instruction::End,
+			],
+		);
+		assert_eq!(float_warnings(&module), vec![]);
+	}
+
+	#[test]
+	fn linter_keeps_warnings_by_code_hash() {
+		let linter = Linter::new(Target::Astar);
+		let code = b"not wasm";
+		let code_hash = Hash::from(blake2_256(code));
+		assert_eq!(linter.warnings(&code_hash), None);
+
+		let warnings = linter.lint(code);
+		assert_eq!(warnings.len(), 1);
+		assert_eq!(linter.clone().warnings(&code_hash), Some(warnings));
+	}
+}
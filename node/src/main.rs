@@ -1,12 +1,17 @@
 //! Substrate Node Template CLI library.
 #![warn(missing_docs)]
 
+mod accounts;
 mod chain_spec;
 #[macro_use]
 mod service;
+mod check_contract;
 mod cli;
 mod command;
+mod fork;
+mod lint;
 mod rpc;
+mod snapshot;
 
 fn main() -> sc_cli::Result<()> {
 	command::run()
@@ -11,26 +11,58 @@ use futures::channel::mpsc::Sender;
 use jsonrpsee::RpcModule;
 use swanky_runtime::{opaque::Block, AccountId, Balance, Hash, Index};
 
+use sc_block_builder::BlockBuilderProvider;
+use sc_client_api::{BlockBackend, ProofProvider, StateBackendFor, StorageProvider};
+use sc_consensus::BlockImport;
 use sc_consensus_manual_seal::{
 	rpc::{ManualSeal, ManualSealApiServer},
 	EngineCommand,
 };
+use sc_rpc::SubscriptionTaskExecutor;
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, ProvideRuntimeApi};
 use sp_block_builder::BlockBuilder;
 use sp_blockchain::{Error as BlockChainError, HeaderBackend, HeaderMetadata};
 
+mod chain_extensions;
+mod dev;
+mod engine;
+mod lint;
+mod pov;
+mod reset;
+mod sealing;
+mod snapshot;
+
+pub use chain_extensions::{ChainExtensions, ChainExtensionsApiServer};
+pub use dev::{Dev, DevApiServer};
+pub use engine::{Engine, EngineApiServer};
+pub use lint::{ContractsLint, ContractsLintApiServer};
+pub use pov::{Pov, PovApiServer};
+pub use reset::{EvictTransactions, Reset, ResetApiServer, ResetNotifications};
+pub use sealing::{Sealing, SealingApiServer, SealingMode, SealingSwitch};
+pub use snapshot::{Snapshot, SnapshotApiServer};
+
 /// Full client dependencies.
 pub struct FullDeps<C, P> {
 	/// The client instance to use.
 	pub client: Arc<C>,
+	/// The backend instance to use.
+	pub backend: Arc<sc_service::TFullBackend<Block>>,
 	/// Transaction pool instance.
 	pub pool: Arc<P>,
 	/// Whether to deny unsafe calls
 	pub deny_unsafe: DenyUnsafe,
 	/// A command stream to send authoring commands to manual seal consensus engine
 	pub command_sink: Sender<EngineCommand<Hash>>,
+	/// Executor to drive the subscriptions
+	pub subscription_executor: SubscriptionTaskExecutor,
+	/// Subscribers to the resets of the chain
+	pub reset_notifications: ResetNotifications,
+	/// Whether instant sealing is paused
+	pub sealing_switch: SealingSwitch,
+	/// Linter of contract code, with `--lint-contracts`
+	pub linter: Option<crate::lint::Linter>,
 }
 
 /// Instantiate all full RPC extensions.
@@ -40,20 +72,60 @@ pub fn create_full<C, P>(
 where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError> + 'static,
-	C: Send + Sync + 'static,
+	C: ProofProvider<Block> + Send + Sync + 'static,
+	C: StorageProvider<Block, sc_service::TFullBackend<Block>> + BlockBackend<Block>,
+	C: BlockBuilderProvider<sc_service::TFullBackend<Block>, Block, C>,
+	for<'a> &'a C: BlockImport<
+		Block,
+		Error = sp_consensus::Error,
+		Transaction = sp_api::TransactionFor<C, Block>,
+	>,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Index>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: BlockBuilder<Block>,
-	P: TransactionPool + 'static,
+	C::Api: ApiExt<Block, StateBackend = StateBackendFor<sc_service::TFullBackend<Block>, Block>>,
+	C::Api: swanky_runtime::chain_extensions::ChainExtensionsApi<Block>,
+	P: TransactionPool<Block = Block, Hash = Hash> + EvictTransactions + 'static,
 {
 	use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
 	use substrate_frame_rpc_system::{System, SystemApiServer};
 
 	let mut io = RpcModule::new(());
-	let FullDeps { client, pool, deny_unsafe, command_sink } = deps;
+	let FullDeps {
+		client,
+		backend,
+		pool,
+		deny_unsafe,
+		command_sink,
+		subscription_executor,
+		reset_notifications,
+		sealing_switch,
+		linter,
+	} = deps;
 
 	io.merge(System::new(client.clone(), pool.clone(), deny_unsafe).into_rpc())?;
 	io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+	io.merge(ChainExtensions::new(client.clone()).into_rpc())?;
+	io.merge(Pov::new(client.clone(), linter.clone()).into_rpc())?;
+	io.merge(ContractsLint::new(linter).into_rpc())?;
+	io.merge(Snapshot::new(client.clone(), deny_unsafe).into_rpc())?;
+	io.merge(
+		Reset::new(
+			client.clone(),
+			backend,
+			pool.clone(),
+			command_sink.clone(),
+			deny_unsafe,
+			subscription_executor,
+			reset_notifications,
+		)
+		.into_rpc(),
+	)?;
+	io.merge(
+		Sealing::new(pool.clone(), command_sink.clone(), sealing_switch, deny_unsafe).into_rpc(),
+	)?;
+	io.merge(Engine::new(client.clone(), pool.clone(), client.clone(), deny_unsafe).into_rpc())?;
+	io.merge(Dev::new(client, pool, deny_unsafe).into_rpc())?;
 
 	// The final RPC extension receives commands for the manual seal consensus engine.
 	io.merge(ManualSeal::new(command_sink).into_rpc())?;
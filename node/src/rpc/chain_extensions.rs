@@ -0,0 +1,58 @@
+//! RPC to discover the chain extensions available to contracts.
+
+use std::sync::Arc;
+
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::generic::BlockId;
+use swanky_runtime::{
+	chain_extensions::{ChainExtensionInfo, ChainExtensionsApi as ChainExtensionsRuntimeApi},
+	opaque::Block,
+	Hash,
+};
+
+/// Error code returned when the runtime API call fails.
+const RUNTIME_ERROR: i32 = 1;
+
+/// Chain extensions RPC methods.
+#[rpc(client, server)]
+pub trait ChainExtensionsApi<BlockHash> {
+	/// Returns the chain extensions registered at the given block, with their IDs and functions.
+	#[method(name = "chainExtensions_list")]
+	fn list(&self, at: Option<BlockHash>) -> RpcResult<Vec<ChainExtensionInfo>>;
+}
+
+/// Provides RPC methods to query the registered chain extensions.
+pub struct ChainExtensions<C> {
+	client: Arc<C>,
+}
+
+impl<C> ChainExtensions<C> {
+	/// Create new `ChainExtensions` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client }
+	}
+}
+
+impl<C> ChainExtensionsApiServer<Hash> for ChainExtensions<C>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: ChainExtensionsRuntimeApi<Block>,
+{
+	fn list(&self, at: Option<Hash>) -> RpcResult<Vec<ChainExtensionInfo>> {
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		self.client.runtime_api().chain_extensions(&at).map_err(|e| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				RUNTIME_ERROR,
+				"Unable to query chain extensions.",
+				Some(e.to_string()),
+			)))
+		})
+	}
+}
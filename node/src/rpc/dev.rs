@@ -0,0 +1,278 @@
+//! Development RPCs driving the dev pallets of the runtime.
+//!
+//! Privileged calls are wrapped into `Sudo::sudo` and submitted to the transaction pool, signed
+//! by the `//Alice` development account, which holds the sudo key of the development chain.
+
+use std::sync::Arc;
+
+use codec::Encode;
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_dev_oracle::FeedKey;
+use sc_rpc_api::DenyUnsafe;
+use sc_transaction_pool_api::{InPoolTransaction, TransactionPool, TransactionSource, TxHash};
+use serde::Deserialize;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{sr25519, Bytes, Pair, H256};
+use sp_keyring::Sr25519Keyring;
+use sp_runtime::{
+	generic::{BlockId, Era},
+	BoundedVec,
+};
+use substrate_frame_rpc_system::AccountNonceApi;
+use swanky_runtime::{
+	opaque::Block, AccountId, AssetId, Balance, BlockNumber, DevOracleCall, DevRandomnessCall,
+	FreeTransactionsCall, Hash, Index, MockChainExtensionCall, Runtime, RuntimeCall, SignedExtra,
+	SignedPayload, SudoCall, UncheckedExtrinsic, VERSION,
+};
+
+/// Error code returned when a development call could not be submitted.
+const DEV_CALL_ERROR: i32 = 9000;
+
+/// Key of an oracle feed, given as `{"symbol": "DOT"}` or `{"asset": 1}`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum FeedKeyParam {
+	/// Feed of a well known symbol.
+	Symbol(String),
+	/// Feed of a `pallet_assets` asset.
+	Asset(AssetId),
+}
+
+impl TryFrom<FeedKeyParam> for FeedKey<AssetId> {
+	type Error = JsonRpseeError;
+
+	fn try_from(param: FeedKeyParam) -> Result<Self, Self::Error> {
+		match param {
+			FeedKeyParam::Symbol(symbol) => BoundedVec::try_from(symbol.into_bytes())
+				.map(FeedKey::Symbol)
+				.map_err(|_| dev_call_error("Feed symbol is too long")),
+			FeedKeyParam::Asset(asset_id) => Ok(FeedKey::Asset(asset_id)),
+		}
+	}
+}
+
+/// Development RPC methods.
+///
+/// Every method returns the hash of the submitted transaction.
+#[rpc(client, server)]
+pub trait DevApi<Hash> {
+	/// Replace the seed of the dev randomness source.
+	#[method(name = "dev_setRandomnessSeed")]
+	async fn set_randomness_seed(&self, seed: H256) -> RpcResult<Hash>;
+
+	/// Set the value of an oracle feed.
+	#[method(name = "dev_setPrice")]
+	async fn set_price(&self, key: FeedKeyParam, value: Balance) -> RpcResult<Hash>;
+
+	/// Schedule updates of an oracle feed, each given as `[block_number, value]`.
+	#[method(name = "dev_schedulePrices")]
+	async fn schedule_prices(
+		&self,
+		key: FeedKeyParam,
+		updates: Vec<(BlockNumber, Balance)>,
+	) -> RpcResult<Hash>;
+
+	/// Answer calls of `func_id` of the chain extension `ext_id` with `output` and `return_code`.
+	#[method(name = "dev_setChainExtensionResponse")]
+	async fn set_chain_extension_response(
+		&self,
+		ext_id: u16,
+		func_id: u16,
+		output: Bytes,
+		return_code: u32,
+	) -> RpcResult<Hash>;
+
+	/// Remove the response registered for `func_id` of the chain extension `ext_id`.
+	#[method(name = "dev_removeChainExtensionResponse")]
+	async fn remove_chain_extension_response(&self, ext_id: u16, func_id: u16) -> RpcResult<Hash>;
+
+	/// Enable or disable free transactions.
+	#[method(name = "dev_setFreeTransactions")]
+	async fn set_free_transactions(&self, enabled: bool) -> RpcResult<Hash>;
+}
+
+/// Provides development RPC methods.
+pub struct Dev<C, P> {
+	client: Arc<C>,
+	pool: Arc<P>,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<C, P> Dev<C, P> {
+	/// Create new `Dev` with the given reference to the client and transaction pool.
+	pub fn new(client: Arc<C>, pool: Arc<P>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, pool, deny_unsafe }
+	}
+}
+
+impl<C, P> Dev<C, P>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AccountNonceApi<Block, AccountId, Index>,
+	P: TransactionPool<Block = Block> + 'static,
+{
+	/// Submit `call` to the pool, to be dispatched with root origin.
+	async fn submit_sudo(&self, call: RuntimeCall) -> RpcResult<TxHash<P>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let signer = Sr25519Keyring::Alice.pair();
+		let info = self.client.info();
+		let at = BlockId::hash(info.best_hash);
+		let nonce = self.account_nonce(&at, signer.public().into())?;
+
+		let call = RuntimeCall::Sudo(SudoCall::sudo { call: Box::new(call) });
+		let extrinsic = create_extrinsic(&signer, call, nonce, info.genesis_hash);
+
+		self.pool
+			.submit_one(&at, TransactionSource::Local, extrinsic.into())
+			.await
+			.map_err(dev_call_error)
+	}
+
+	/// Returns the next nonce of `account`, taking its transactions in the pool into account.
+	fn account_nonce(&self, at: &BlockId<Block>, account: AccountId) -> RpcResult<Index> {
+		let mut nonce = self
+			.client
+			.runtime_api()
+			.account_nonce(at, account.clone())
+			.map_err(dev_call_error)?;
+
+		let mut current_tag = (account.clone(), nonce).encode();
+		for tx in self.pool.ready() {
+			// Transactions are ordered by their nonce, so a single pass is enough.
+			if tx.provides().first() == Some(&current_tag) {
+				nonce += 1;
+				current_tag = (account.clone(), nonce).encode();
+			}
+		}
+
+		Ok(nonce)
+	}
+}
+
+#[async_trait]
+impl<C, P> DevApiServer<TxHash<P>> for Dev<C, P>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: AccountNonceApi<Block, AccountId, Index>,
+	P: TransactionPool<Block = Block> + 'static,
+{
+	async fn set_randomness_seed(&self, seed: H256) -> RpcResult<TxHash<P>> {
+		self.submit_sudo(RuntimeCall::DevRandomness(DevRandomnessCall::set_seed { seed }))
+			.await
+	}
+
+	async fn set_price(&self, key: FeedKeyParam, value: Balance) -> RpcResult<TxHash<P>> {
+		let key = key.try_into()?;
+
+		self.submit_sudo(RuntimeCall::DevOracle(DevOracleCall::set_price { key, value }))
+			.await
+	}
+
+	async fn schedule_prices(
+		&self,
+		key: FeedKeyParam,
+		updates: Vec<(BlockNumber, Balance)>,
+	) -> RpcResult<TxHash<P>> {
+		let key = key.try_into()?;
+		let updates = BoundedVec::try_from(updates)
+			.map_err(|_| dev_call_error("Too many updates scheduled at once"))?;
+
+		self.submit_sudo(RuntimeCall::DevOracle(DevOracleCall::schedule_prices { key, updates }))
+			.await
+	}
+
+	async fn set_chain_extension_response(
+		&self,
+		ext_id: u16,
+		func_id: u16,
+		output: Bytes,
+		return_code: u32,
+	) -> RpcResult<TxHash<P>> {
+		let output = BoundedVec::try_from(output.to_vec())
+			.map_err(|_| dev_call_error("Response output is too long"))?;
+
+		self.submit_sudo(RuntimeCall::MockChainExtension(MockChainExtensionCall::set_response {
+			ext_id,
+			func_id,
+			output,
+			return_code,
+		}))
+		.await
+	}
+
+	async fn remove_chain_extension_response(
+		&self,
+		ext_id: u16,
+		func_id: u16,
+	) -> RpcResult<TxHash<P>> {
+		self.submit_sudo(RuntimeCall::MockChainExtension(MockChainExtensionCall::remove_response {
+			ext_id,
+			func_id,
+		}))
+		.await
+	}
+
+	async fn set_free_transactions(&self, enabled: bool) -> RpcResult<TxHash<P>> {
+		self.submit_sudo(RuntimeCall::FreeTransactions(FreeTransactionsCall::set_enabled {
+			enabled,
+		}))
+		.await
+	}
+}
+
+/// Build an immortal transaction of `call`, signed by `signer`.
+fn create_extrinsic(
+	signer: &sr25519::Pair,
+	call: RuntimeCall,
+	nonce: Index,
+	genesis_hash: Hash,
+) -> UncheckedExtrinsic {
+	let extra: SignedExtra = (
+		frame_system::CheckNonZeroSender::<Runtime>::new(),
+		frame_system::CheckSpecVersion::<Runtime>::new(),
+		frame_system::CheckTxVersion::<Runtime>::new(),
+		frame_system::CheckGenesis::<Runtime>::new(),
+		frame_system::CheckEra::<Runtime>::from(Era::Immortal),
+		frame_system::CheckNonce::<Runtime>::from(nonce),
+		pallet_pov_limit::CheckProofSize::<Runtime>::new(),
+		frame_system::CheckWeight::<Runtime>::new(),
+		pallet_free_transactions::ChargeAssetTxPayment::<Runtime>::from(0, None),
+	);
+	let payload = SignedPayload::from_raw(
+		call.clone(),
+		extra.clone(),
+		(
+			(),
+			VERSION.spec_version,
+			VERSION.transaction_version,
+			genesis_hash,
+			genesis_hash,
+			(),
+			(),
+			(),
+			(),
+		),
+	);
+	let signature = payload.using_encoded(|e| signer.sign(e));
+
+	UncheckedExtrinsic::new_signed(
+		call,
+		AccountId::from(signer.public()).into(),
+		signature.into(),
+		extra,
+	)
+}
+
+fn dev_call_error<E: ToString>(error: E) -> JsonRpseeError {
+	JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+		DEV_CALL_ERROR,
+		"Unable to submit development call",
+		Some(error.to_string()),
+	)))
+}
@@ -0,0 +1,181 @@
+//! RPC to seal a block with an exact, ordered list of extrinsics.
+//!
+//! `engine_createBlock` leaves the choice and order of the extrinsics to the proposer, which takes
+//! them from the pool. This builds the block directly instead, so that ordering-dependent
+//! behaviour, e.g. front-running, can be reproduced. Pause instant sealing first to keep the
+//! pending transactions in the pool.
+
+use std::{collections::HashMap, sync::Arc};
+
+use codec::Decode;
+use jsonrpsee::{
+	core::{async_trait, Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sc_block_builder::{BlockBuilderApi, BlockBuilderProvider};
+use sc_client_api::StateBackendFor;
+use sc_consensus::{
+	BlockImport, BlockImportParams, ForkChoiceStrategy, ImportResult, StateAction, StorageChanges,
+};
+use sc_consensus_manual_seal::CreatedBlock;
+use sc_rpc_api::DenyUnsafe;
+use sc_service::TFullBackend;
+use sc_transaction_pool_api::{InPoolTransaction, TransactionPool};
+use serde::Deserialize;
+use sp_api::{ApiExt, ProvideRuntimeApi, TransactionFor};
+use sp_blockchain::HeaderBackend;
+use sp_consensus::BlockOrigin;
+use sp_core::Bytes;
+use sp_inherents::InherentDataProvider;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, Header as HeaderT},
+	OpaqueExtrinsic,
+};
+use swanky_runtime::{opaque::Block, Hash};
+
+/// Error code returned when the block could not be created.
+const CREATE_BLOCK_ERROR: i32 = 9400;
+
+/// Extrinsic to include, given as `{"hash": "0x..."}` of a pool transaction or `{"raw": "0x..."}`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExtrinsicParam {
+	/// Transaction of the pool with the given hash.
+	Hash(Hash),
+	/// SCALE encoded extrinsic, like for `author_submitExtrinsic`.
+	Raw(Bytes),
+}
+
+/// Engine RPC methods.
+#[rpc(client, server)]
+pub trait EngineApi<BlockHash> {
+	/// Seal a block with exactly the given extrinsics, in the given order, after the inherents.
+	///
+	/// The block is built on `parent_hash`, the best block by default, with the timestamp in
+	/// milliseconds if given. It fails if any of the extrinsics cannot be applied.
+	#[method(name = "engine_createBlockWithExtrinsics")]
+	async fn create_block_with_extrinsics(
+		&self,
+		extrinsics: Vec<ExtrinsicParam>,
+		finalize: bool,
+		parent_hash: Option<BlockHash>,
+		timestamp: Option<u64>,
+	) -> RpcResult<CreatedBlock<BlockHash>>;
+}
+
+/// Provides RPC methods to seal blocks with chosen extrinsics.
+pub struct Engine<C, P, BI> {
+	client: Arc<C>,
+	pool: Arc<P>,
+	block_import: BI,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<C, P, BI> Engine<C, P, BI> {
+	/// Create new `Engine` importing the blocks it builds into `block_import`.
+	pub fn new(client: Arc<C>, pool: Arc<P>, block_import: BI, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, pool, block_import, deny_unsafe }
+	}
+}
+
+impl<C, P, BI> Engine<C, P, BI>
+where
+	P: TransactionPool<Block = Block, Hash = Hash> + 'static,
+{
+	/// Returns the extrinsic `param` refers to.
+	fn extrinsic(&self, index: usize, param: ExtrinsicParam) -> RpcResult<OpaqueExtrinsic> {
+		match param {
+			ExtrinsicParam::Hash(hash) => self
+				.pool
+				.ready_transaction(&hash)
+				.or_else(|| {
+					self.pool.futures().into_iter().find(|tx| tx.hash() == &hash).map(Arc::new)
+				})
+				.map(|tx| tx.data().clone())
+				.ok_or_else(|| {
+					create_block_error(format!(
+						"Extrinsic #{}: {:?} is not in the pool",
+						index, hash
+					))
+				}),
+			ExtrinsicParam::Raw(bytes) => OpaqueExtrinsic::decode(&mut &bytes[..])
+				.map_err(|e| create_block_error(format!("Extrinsic #{}: {}", index, e))),
+		}
+	}
+}
+
+#[async_trait]
+impl<C, P, BI> EngineApiServer<Hash> for Engine<C, P, BI>
+where
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C: BlockBuilderProvider<TFullBackend<Block>, Block, C> + Send + Sync + 'static,
+	C::Api: BlockBuilderApi<Block>,
+	C::Api: ApiExt<Block, StateBackend = StateBackendFor<TFullBackend<Block>, Block>>,
+	P: TransactionPool<Block = Block, Hash = Hash> + 'static,
+	BI: BlockImport<Block, Error = sp_consensus::Error, Transaction = TransactionFor<C, Block>>,
+	BI: Clone + Send + Sync + 'static,
+{
+	async fn create_block_with_extrinsics(
+		&self,
+		extrinsics: Vec<ExtrinsicParam>,
+		finalize: bool,
+		parent_hash: Option<Hash>,
+		timestamp: Option<u64>,
+	) -> RpcResult<CreatedBlock<Hash>> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let extrinsics = extrinsics
+			.into_iter()
+			.enumerate()
+			.map(|(index, param)| self.extrinsic(index, param))
+			.collect::<RpcResult<Vec<_>>>()?;
+
+		let parent_hash = parent_hash.unwrap_or_else(|| self.client.info().best_hash);
+		let timestamp = match timestamp {
+			Some(timestamp) => sp_timestamp::InherentDataProvider::new(timestamp.into()),
+			None => sp_timestamp::InherentDataProvider::from_system_time(),
+		};
+		let inherent_data = timestamp.create_inherent_data().await.map_err(create_block_error)?;
+
+		let built = {
+			let mut builder = self
+				.client
+				.new_block_at(&BlockId::Hash(parent_hash), Default::default(), false)
+				.map_err(create_block_error)?;
+			for inherent in builder.create_inherents(inherent_data).map_err(create_block_error)? {
+				builder.push(inherent).map_err(create_block_error)?;
+			}
+			for (index, extrinsic) in extrinsics.into_iter().enumerate() {
+				builder
+					.push(extrinsic)
+					.map_err(|e| create_block_error(format!("Extrinsic #{}: {}", index, e)))?;
+			}
+			builder.build().map_err(create_block_error)?
+		};
+
+		let (header, body) = built.block.deconstruct();
+		let hash = header.hash();
+		let mut params = BlockImportParams::new(BlockOrigin::Own, header);
+		params.body = Some(body);
+		params.state_action =
+			StateAction::ApplyChanges(StorageChanges::Changes(built.storage_changes));
+		params.finalized = finalize;
+		params.fork_choice = Some(ForkChoiceStrategy::LongestChain);
+
+		match self.block_import.clone().import_block(params, HashMap::new()).await {
+			Ok(ImportResult::Imported(aux)) => Ok(CreatedBlock { hash, aux }),
+			Ok(other) => Err(create_block_error(format!("{:?}", other))),
+			Err(e) => Err(create_block_error(e)),
+		}
+	}
+}
+
+fn create_block_error<E: ToString>(error: E) -> JsonRpseeError {
+	JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+		CREATE_BLOCK_ERROR,
+		"Unable to create the block.",
+		Some(error.to_string()),
+	)))
+}
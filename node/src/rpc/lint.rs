@@ -0,0 +1,42 @@
+//! RPC to check contract code against the limits of production chains.
+
+use jsonrpsee::{core::RpcResult, proc_macros::rpc};
+use sp_core::Bytes;
+use swanky_runtime::Hash;
+
+use crate::lint::{lint, Linter, Target};
+
+/// Contracts lint RPC methods.
+#[rpc(client, server)]
+pub trait ContractsLintApi<BlockHash> {
+	/// Returns the problems `target` would reject `code` for, empty if there are none.
+	#[method(name = "contracts_lint")]
+	fn lint(&self, code: Bytes, target: Target) -> RpcResult<Vec<String>>;
+
+	/// Returns the problems found in the code with `code_hash` by `--lint-contracts`, when it was
+	/// uploaded or dry-run through `pov_call`, or `null` if it was not linted.
+	#[method(name = "contracts_lintWarnings")]
+	fn lint_warnings(&self, code_hash: BlockHash) -> RpcResult<Option<Vec<String>>>;
+}
+
+/// Provides RPC methods to lint contract code.
+pub struct ContractsLint {
+	linter: Option<Linter>,
+}
+
+impl ContractsLint {
+	/// Create new `ContractsLint` reading the warnings kept by `linter`, if contracts are linted.
+	pub fn new(linter: Option<Linter>) -> Self {
+		Self { linter }
+	}
+}
+
+impl ContractsLintApiServer<Hash> for ContractsLint {
+	fn lint(&self, code: Bytes, target: Target) -> RpcResult<Vec<String>> {
+		Ok(lint(&code, &target.into()).iter().map(ToString::to_string).collect())
+	}
+
+	fn lint_warnings(&self, code_hash: Hash) -> RpcResult<Option<Vec<String>>> {
+		Ok(self.linter.as_ref().and_then(|linter| linter.warnings(&code_hash)))
+	}
+}
@@ -0,0 +1,92 @@
+//! RPC to measure the proof size of runtime API calls, e.g. contract dry-runs.
+
+use std::sync::Arc;
+
+use codec::{Decode, Encode};
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sc_client_api::ProofProvider;
+use serde::Serialize;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use swanky_runtime::{opaque::Block, AccountId, Hash};
+
+use crate::lint::Linter;
+
+/// Error code returned when the runtime API call fails.
+const CALL_ERROR: i32 = 1;
+
+/// Result of a runtime API call together with the size of its storage proof.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PovCallResult {
+	/// SCALE encoded result of the call, as returned by `state_call`.
+	pub result: Bytes,
+	/// Size of the SCALE encoded storage proof of the call, in bytes.
+	pub proof_size: u64,
+	/// Problems production chains would reject the code of a `ContractsApi_upload_code` dry-run
+	/// for, with `--lint-contracts`.
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub lint_warnings: Option<Vec<String>>,
+}
+
+/// PoV RPC methods.
+#[rpc(client, server)]
+pub trait PovApi<BlockHash> {
+	/// Call a runtime API like `state_call`, recording the storage proof of the call.
+	///
+	/// Contract dry-runs are measured by calling `ContractsApi_call`, `ContractsApi_instantiate`
+	/// or `ContractsApi_upload_code` with the same data as for `state_call`. The code of
+	/// `ContractsApi_upload_code` is linted as well with `--lint-contracts`.
+	#[method(name = "pov_call")]
+	fn call(&self, method: String, data: Bytes, at: Option<BlockHash>) -> RpcResult<PovCallResult>;
+}
+
+/// Provides RPC methods to measure proof sizes.
+pub struct Pov<C> {
+	client: Arc<C>,
+	linter: Option<Linter>,
+}
+
+impl<C> Pov<C> {
+	/// Create new `Pov` with the given reference to the client, linting uploaded code with
+	/// `linter`.
+	pub fn new(client: Arc<C>, linter: Option<Linter>) -> Self {
+		Self { client, linter }
+	}
+}
+
+impl<C> PovApiServer<Hash> for Pov<C>
+where
+	C: ProofProvider<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+{
+	fn call(&self, method: String, data: Bytes, at: Option<Hash>) -> RpcResult<PovCallResult> {
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let (result, proof) = self.client.execution_proof(at, &method, &data).map_err(|e| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				CALL_ERROR,
+				"Unable to execute the call.",
+				Some(e.to_string()),
+			)))
+		})?;
+
+		// The origin and code come first among the arguments of `upload_code`.
+		let lint_warnings = match &self.linter {
+			Some(linter) if method == "ContractsApi_upload_code" =>
+				<(AccountId, Vec<u8>)>::decode(&mut &data[..])
+					.ok()
+					.map(|(_, code)| linter.lint(&code)),
+			_ => None,
+		};
+
+		Ok(PovCallResult {
+			result: result.into(),
+			proof_size: proof.encoded_size() as u64,
+			lint_warnings,
+		})
+	}
+}
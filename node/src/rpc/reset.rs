@@ -0,0 +1,192 @@
+//! RPC to reset the chain to its genesis block while the node keeps running.
+//!
+//! Blocks are reverted in the backend, finalized ones included, which requires the state of every
+//! block to be kept, as it is by default on this node. Connections stay open. The usual block
+//! subscriptions only report imports, so an empty block is sealed on top of genesis right after
+//! the reset, and `dev_subscribeReset` reports the reset itself.
+
+use std::sync::{Arc, Mutex};
+
+use futures::{
+	channel::mpsc::{self, Sender},
+	FutureExt,
+};
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::{
+		error::{CallError, ErrorObject},
+		SubscriptionResult,
+	},
+	SubscriptionSink,
+};
+use sc_client_api::Backend;
+use sc_consensus_manual_seal::EngineCommand;
+use sc_rpc::SubscriptionTaskExecutor;
+use sc_rpc_api::DenyUnsafe;
+use sc_service::TFullBackend;
+use sc_transaction_pool::{BasicPool, ChainApi, ValidatedTransaction};
+use sc_transaction_pool_api::{error::Error as TxPoolError, InPoolTransaction, TransactionPool};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::traits::Zero;
+use swanky_runtime::{opaque::Block, Hash};
+
+/// Error code returned when the chain could not be reset.
+const RESET_ERROR: i32 = 9200;
+
+/// Subscribers to the resets of the chain, shared by every instance of the RPC module.
+#[derive(Clone, Default)]
+pub struct ResetNotifications {
+	sinks: Arc<Mutex<Vec<mpsc::UnboundedSender<Hash>>>>,
+}
+
+impl ResetNotifications {
+	/// Returns a stream of the genesis hash, yielded on every reset.
+	fn subscribe(&self) -> mpsc::UnboundedReceiver<Hash> {
+		let (sender, receiver) = mpsc::unbounded();
+		self.sinks.lock().expect("lock is not poisoned; qed").push(sender);
+		receiver
+	}
+
+	/// Notifies the subscribers of a reset, dropping those that are gone.
+	fn notify(&self, genesis_hash: Hash) {
+		let mut sinks = self.sinks.lock().expect("lock is not poisoned; qed");
+		sinks.retain(|sink| sink.unbounded_send(genesis_hash).is_ok());
+	}
+}
+
+/// Transaction pool that can drop transactions without banning them.
+///
+/// `TransactionPool::remove_invalid` bans the hashes it removes, which would reject the same
+/// transactions when a test suite submits them again after a reset.
+pub trait EvictTransactions {
+	/// Removes the given transactions from the pool, leaving them free to be resubmitted.
+	fn evict(&self, hashes: Vec<Hash>);
+}
+
+impl<A> EvictTransactions for BasicPool<A, Block>
+where
+	A: ChainApi<Block = Block> + 'static,
+{
+	fn evict(&self, hashes: Vec<Hash>) {
+		// Unlike `remove_invalid`, resubmitting transactions as invalid does not ban them.
+		let evicted = hashes
+			.into_iter()
+			.map(|hash| {
+				(hash, ValidatedTransaction::Invalid(hash, TxPoolError::ImmediatelyDropped.into()))
+			})
+			.collect();
+		self.pool().validated_pool().resubmit(evicted);
+	}
+}
+
+/// Reset RPC methods.
+#[rpc(client, server)]
+pub trait ResetApi<BlockHash> {
+	/// Revert every block down to genesis and clear the transaction pool.
+	///
+	/// The genesis stays the one the node was started with. Returns its hash. An empty block is
+	/// sealed and finalized on top of it right after, which the block subscriptions report.
+	#[method(name = "dev_reset")]
+	fn reset(&self) -> RpcResult<BlockHash>;
+
+	/// Subscribe to the resets of the chain, each notified with the hash of the genesis block.
+	#[subscription(
+		name = "dev_subscribeReset" => "dev_chainReset",
+		unsubscribe = "dev_unsubscribeReset",
+		item = BlockHash
+	)]
+	fn subscribe_reset(&self);
+}
+
+/// Provides RPC methods to reset the chain.
+pub struct Reset<C, P> {
+	client: Arc<C>,
+	backend: Arc<TFullBackend<Block>>,
+	pool: Arc<P>,
+	command_sink: Sender<EngineCommand<Hash>>,
+	deny_unsafe: DenyUnsafe,
+	executor: SubscriptionTaskExecutor,
+	notifications: ResetNotifications,
+}
+
+impl<C, P> Reset<C, P> {
+	/// Create new `Reset` with the given references to the client, backend and transaction pool.
+	pub fn new(
+		client: Arc<C>,
+		backend: Arc<TFullBackend<Block>>,
+		pool: Arc<P>,
+		command_sink: Sender<EngineCommand<Hash>>,
+		deny_unsafe: DenyUnsafe,
+		executor: SubscriptionTaskExecutor,
+		notifications: ResetNotifications,
+	) -> Self {
+		Self { client, backend, pool, command_sink, deny_unsafe, executor, notifications }
+	}
+}
+
+impl<C, P> ResetApiServer<Hash> for Reset<C, P>
+where
+	C: HeaderBackend<Block> + Send + Sync + 'static,
+	P: TransactionPool<Block = Block, Hash = Hash> + EvictTransactions + 'static,
+{
+	fn reset(&self) -> RpcResult<Hash> {
+		self.deny_unsafe.check_if_safe()?;
+
+		// Blocks are not imported while the chain is reverted.
+		let _lock = self.backend.get_import_lock().write();
+		let info = self.client.info();
+		if !self.backend.have_state_at(info.genesis_hash, Zero::zero()) {
+			return Err(reset_error(
+				"The genesis state was pruned, start the node with `--state-pruning archive`",
+			))
+		}
+
+		let (reverted, _) = self.backend.revert(info.best_number, true).map_err(reset_error)?;
+		if reverted < info.best_number {
+			return Err(reset_error(format!(
+				"Only {} of {} blocks could be reverted, start the node with `--state-pruning \
+				 archive`",
+				reverted, info.best_number
+			)))
+		}
+
+		let pending = self
+			.pool
+			.ready()
+			.map(|tx| tx.hash().clone())
+			.chain(self.pool.futures().iter().map(|tx| tx.hash().clone()))
+			.collect::<Vec<_>>();
+		self.pool.evict(pending);
+
+		log::info!("Reset the chain to genesis, reverting {} blocks", reverted);
+		self.notifications.notify(info.genesis_hash);
+		self.command_sink
+			.clone()
+			.try_send(EngineCommand::SealNewBlock {
+				create_empty: true,
+				finalize: true,
+				parent_hash: Some(info.genesis_hash),
+				sender: None,
+			})
+			.map_err(reset_error)?;
+		Ok(info.genesis_hash)
+	}
+
+	fn subscribe_reset(&self, sink: SubscriptionSink) -> SubscriptionResult {
+		let stream = self.notifications.subscribe();
+		let fut = async move {
+			sink.pipe_from_stream(stream).await;
+		};
+		self.executor.spawn("swanky-rpc-subscription", Some("rpc"), fut.boxed());
+		Ok(())
+	}
+}
+
+fn reset_error<E: ToString>(error: E) -> JsonRpseeError {
+	JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+		RESET_ERROR,
+		"Unable to reset the chain.",
+		Some(error.to_string()),
+	)))
+}
@@ -0,0 +1,131 @@
+//! RPC to switch between instant and manual sealing while the node runs.
+//!
+//! Blocks are sealed as soon as transactions enter the pool unless instant sealing is paused, in
+//! which case they accumulate in the pool until `engine_createBlock` is called.
+
+use std::sync::{
+	atomic::{AtomicBool, Ordering},
+	Arc,
+};
+
+use futures::channel::mpsc::Sender;
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sc_consensus_manual_seal::EngineCommand;
+use sc_rpc_api::DenyUnsafe;
+use sc_transaction_pool_api::TransactionPool;
+use serde::Serialize;
+use swanky_runtime::{opaque::Block, Hash};
+
+/// Error code returned when the pending transactions could not be sealed.
+const SEAL_ERROR: i32 = 9300;
+
+/// How blocks are sealed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SealingMode {
+	/// As soon as transactions enter the pool, and on `engine_createBlock`.
+	Instant,
+	/// Only on `engine_createBlock`.
+	Manual,
+}
+
+/// Whether instant sealing is paused, shared by the RPC and the sealing task.
+#[derive(Clone, Default)]
+pub struct SealingSwitch {
+	paused: Arc<AtomicBool>,
+}
+
+impl SealingSwitch {
+	/// Returns the current sealing mode.
+	pub fn mode(&self) -> SealingMode {
+		if self.paused.load(Ordering::Relaxed) {
+			SealingMode::Manual
+		} else {
+			SealingMode::Instant
+		}
+	}
+
+	fn set_paused(&self, paused: bool) {
+		self.paused.store(paused, Ordering::Relaxed);
+	}
+}
+
+/// Sealing RPC methods.
+#[rpc(client, server)]
+pub trait SealingApi {
+	/// Pause instant sealing, so that transactions stay in the pool until `engine_createBlock`.
+	#[method(name = "dev_pauseSealing")]
+	fn pause(&self) -> RpcResult<SealingMode>;
+
+	/// Resume instant sealing, sealing the transactions that accumulated in the pool.
+	#[method(name = "dev_resumeSealing")]
+	fn resume(&self) -> RpcResult<SealingMode>;
+
+	/// Returns the current sealing mode, `instant` or `manual`.
+	#[method(name = "dev_sealingMode")]
+	fn mode(&self) -> RpcResult<SealingMode>;
+}
+
+/// Provides RPC methods to switch the sealing mode.
+pub struct Sealing<P> {
+	pool: Arc<P>,
+	command_sink: Sender<EngineCommand<Hash>>,
+	switch: SealingSwitch,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<P> Sealing<P> {
+	/// Create new `Sealing` controlling the given switch.
+	pub fn new(
+		pool: Arc<P>,
+		command_sink: Sender<EngineCommand<Hash>>,
+		switch: SealingSwitch,
+		deny_unsafe: DenyUnsafe,
+	) -> Self {
+		Self { pool, command_sink, switch, deny_unsafe }
+	}
+}
+
+impl<P> SealingApiServer for Sealing<P>
+where
+	P: TransactionPool<Block = Block> + 'static,
+{
+	fn pause(&self) -> RpcResult<SealingMode> {
+		self.deny_unsafe.check_if_safe()?;
+
+		self.switch.set_paused(true);
+		Ok(self.switch.mode())
+	}
+
+	fn resume(&self) -> RpcResult<SealingMode> {
+		self.deny_unsafe.check_if_safe()?;
+
+		self.switch.set_paused(false);
+		if self.pool.status().ready > 0 {
+			self.command_sink
+				.clone()
+				.try_send(EngineCommand::SealNewBlock {
+					create_empty: false,
+					finalize: true,
+					parent_hash: None,
+					sender: None,
+				})
+				.map_err(|e| {
+					JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+						SEAL_ERROR,
+						"Unable to seal the pending transactions.",
+						Some(e.to_string()),
+					)))
+				})?;
+		}
+		Ok(self.switch.mode())
+	}
+
+	fn mode(&self) -> RpcResult<SealingMode> {
+		Ok(self.switch.mode())
+	}
+}
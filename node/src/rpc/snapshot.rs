@@ -0,0 +1,74 @@
+//! RPC to export the state of the chain to a snapshot file, loaded with `--load-snapshot`.
+
+use std::{path::PathBuf, sync::Arc};
+
+use jsonrpsee::{
+	core::{Error as JsonRpseeError, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sc_client_api::{BlockBackend, StorageProvider};
+use sc_rpc_api::DenyUnsafe;
+use sc_service::TFullBackend;
+use sp_blockchain::HeaderBackend;
+use swanky_runtime::{opaque::Block, Hash};
+
+use crate::snapshot;
+
+/// Error code returned when the snapshot could not be exported.
+const EXPORT_ERROR: i32 = 9100;
+
+/// Snapshot RPC methods.
+#[rpc(client, server)]
+pub trait SnapshotApi<BlockHash> {
+	/// Write the state at block `at`, the best block by default, to the file at `path` on the
+	/// node's machine, along with up to `blocks` blocks leading to it.
+	///
+	/// Returns the hash of the exported block.
+	#[method(name = "dev_exportSnapshot")]
+	fn export_snapshot(
+		&self,
+		path: PathBuf,
+		at: Option<BlockHash>,
+		blocks: Option<u32>,
+	) -> RpcResult<BlockHash>;
+}
+
+/// Provides RPC methods to export snapshots.
+pub struct Snapshot<C> {
+	client: Arc<C>,
+	deny_unsafe: DenyUnsafe,
+}
+
+impl<C> Snapshot<C> {
+	/// Create new `Snapshot` with the given reference to the client.
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
+		Self { client, deny_unsafe }
+	}
+}
+
+impl<C> SnapshotApiServer<Hash> for Snapshot<C>
+where
+	C: StorageProvider<Block, TFullBackend<Block>> + BlockBackend<Block> + HeaderBackend<Block>,
+	C: Send + Sync + 'static,
+{
+	fn export_snapshot(
+		&self,
+		path: PathBuf,
+		at: Option<Hash>,
+		blocks: Option<u32>,
+	) -> RpcResult<Hash> {
+		self.deny_unsafe.check_if_safe()?;
+
+		let at = at.unwrap_or_else(|| self.client.info().best_hash);
+		snapshot::export(&*self.client, at, blocks.unwrap_or_default(), &path).map_err(|e| {
+			JsonRpseeError::Call(CallError::Custom(ErrorObject::owned(
+				EXPORT_ERROR,
+				"Unable to export the snapshot.",
+				Some(e),
+			)))
+		})?;
+
+		Ok(at)
+	}
+}
@@ -1,12 +1,17 @@
 //! Service and ServiceFactory implementation. Specialized wrapper over substrate service.
 
+use crate::cli::DevParams;
+use codec::Decode;
 use futures::prelude::*;
 
+use sc_client_api::StorageProvider;
 pub use sc_executor::NativeElseWasmExecutor;
 use sc_keystore::LocalKeystore;
 use sc_service::{error::Error as ServiceError, Configuration, TaskManager};
 use sc_telemetry::{Telemetry, TelemetryWorker};
 use sc_transaction_pool_api::TransactionPool;
+use sp_blockchain::HeaderBackend;
+use sp_core::{hashing::twox_128, storage::StorageKey};
 use std::sync::Arc;
 use swanky_runtime::{self, opaque::Block, RuntimeApi};
 // Our native executor instance.
@@ -117,8 +122,21 @@ fn remote_keystore(_url: &String) -> Result<Arc<LocalKeystore>, &'static str> {
 	Err("Remote Keystore not supported.")
 }
 
+/// Returns the proof size limit of blocks stored by `PovLimit` at the best block, if any.
+fn stored_max_block_pov_size(client: &FullClient) -> Option<u64> {
+	let key = StorageKey([twox_128(b"PovLimit"), twox_128(b"MaxBlockProofSize")].concat());
+	client
+		.storage(client.info().best_hash, &key)
+		.ok()
+		.flatten()
+		.and_then(|data| u64::decode(&mut &data.0[..]).ok())
+}
+
 /// Builds a new service for a full client.
-pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
+///
+/// With `dev.max_block_pov_size`, or else the block limit of `PovLimit` when the node starts,
+/// blocks are built with storage proof recording and limited to that size, including their proof.
+pub fn new_full(config: Configuration, dev: DevParams) -> Result<TaskManager, ServiceError> {
 	let sc_service::PartialComponents {
 		client,
 		backend,
@@ -163,17 +181,28 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 
 	let prometheus_registry = config.prometheus_registry().cloned();
 	let (rpc_command_sink, rpc_commands_stream) = futures::channel::mpsc::channel(1000);
+	let sealing_switch = crate::rpc::SealingSwitch::default();
+	let linter = dev.lint_contracts.map(crate::lint::Linter::new);
 
 	let rpc_extensions_builder = {
 		let client = client.clone();
+		let backend = backend.clone();
 		let pool = transaction_pool.clone();
+		let reset_notifications = crate::rpc::ResetNotifications::default();
+		let sealing_switch = sealing_switch.clone();
+		let linter = linter.clone();
 
-		Box::new(move |deny_unsafe, _| {
+		Box::new(move |deny_unsafe, subscription_executor| {
 			let deps = crate::rpc::FullDeps {
 				client: client.clone(),
+				backend: backend.clone(),
 				pool: pool.clone(),
 				deny_unsafe,
 				command_sink: rpc_command_sink.clone(),
+				subscription_executor,
+				reset_notifications: reset_notifications.clone(),
+				sealing_switch: sealing_switch.clone(),
+				linter: linter.clone(),
 			};
 			crate::rpc::create_full(deps).map_err(Into::into)
 		})
@@ -193,43 +222,86 @@ pub fn new_full(config: Configuration) -> Result<TaskManager, ServiceError> {
 		telemetry: telemetry.as_mut(),
 	})?;
 
-	let proposer = sc_basic_authorship::ProposerFactory::new(
-		task_manager.spawn_handle(),
-		client.clone(),
-		transaction_pool.clone(),
-		prometheus_registry.as_ref(),
-		telemetry.as_ref().map(|x| x.handle()),
-	);
+	if let Some(linter) = linter {
+		task_manager.spawn_handle().spawn(
+			"contracts-lint",
+			None,
+			crate::lint::lint_stored_code(client.clone(), linter),
+		);
+	}
 
-	let pool_import_commands_stream =
-		transaction_pool.clone().import_notification_stream().map(|_| {
-			sc_consensus_manual_seal::EngineCommand::SealNewBlock {
-				create_empty: false,
-				finalize: true,
-				parent_hash: None,
-				sender: None,
-			}
+	// Transactions entering the pool are sealed right away, unless instant sealing is paused.
+	let pool_import_commands_stream = transaction_pool
+		.clone()
+		.import_notification_stream()
+		.filter(move |_| future::ready(sealing_switch.mode() == crate::rpc::SealingMode::Instant))
+		.map(|_| sc_consensus_manual_seal::EngineCommand::SealNewBlock {
+			create_empty: false,
+			finalize: true,
+			parent_hash: None,
+			sender: None,
 		});
 
 	let commands_stream = stream::select(rpc_commands_stream, pool_import_commands_stream);
+	let create_inherent_data_providers =
+		move |_, ()| async move { Ok(sp_timestamp::InherentDataProvider::from_system_time()) };
 
-	let params = sc_consensus_manual_seal::ManualSealParams {
-		block_import: client.clone(),
-		env: proposer,
-		client,
-		pool: transaction_pool,
-		commands_stream,
-		select_chain,
-		consensus_data_provider: None,
-		create_inherent_data_providers: move |_, ()| async move {
-			Ok(sp_timestamp::InherentDataProvider::from_system_time())
+	// The limit of `PovLimit` covers chains whose genesis sets it, e.g. the production preset.
+	let max_block_pov_size = dev.max_block_pov_size.or_else(|| stored_max_block_pov_size(&client));
+
+	// The proposer records the storage proof only when it is limited, which changes its type.
+	let authorship_future = match max_block_pov_size {
+		Some(max_block_pov_size) => {
+			let mut proposer = sc_basic_authorship::ProposerFactory::with_proof_recording(
+				task_manager.spawn_handle(),
+				client.clone(),
+				transaction_pool.clone(),
+				prometheus_registry.as_ref(),
+				telemetry.as_ref().map(|x| x.handle()),
+			);
+			proposer.set_default_block_size_limit(max_block_pov_size as usize);
+
+			future::Either::Left(sc_consensus_manual_seal::run_manual_seal(
+				sc_consensus_manual_seal::ManualSealParams {
+					block_import: client.clone(),
+					env: proposer,
+					client,
+					pool: transaction_pool,
+					commands_stream,
+					select_chain,
+					consensus_data_provider: None,
+					create_inherent_data_providers,
+				},
+			))
+		},
+		None => {
+			let proposer = sc_basic_authorship::ProposerFactory::new(
+				task_manager.spawn_handle(),
+				client.clone(),
+				transaction_pool.clone(),
+				prometheus_registry.as_ref(),
+				telemetry.as_ref().map(|x| x.handle()),
+			);
+
+			future::Either::Right(sc_consensus_manual_seal::run_manual_seal(
+				sc_consensus_manual_seal::ManualSealParams {
+					block_import: client.clone(),
+					env: proposer,
+					client,
+					pool: transaction_pool,
+					commands_stream,
+					select_chain,
+					consensus_data_provider: None,
+					create_inherent_data_providers,
+				},
+			))
 		},
 	};
 
 	task_manager.spawn_essential_handle().spawn_blocking(
 		"instant-and-manual-seal",
 		None,
-		sc_consensus_manual_seal::run_manual_seal(params),
+		authorship_future,
 	);
 
 	network_starter.start_network();
@@ -0,0 +1,217 @@
+//! Portable snapshots of the chain state.
+//!
+//! A snapshot holds the complete state at a block, including the child tries of contracts, and
+//! optionally the blocks leading to it. It is written by the `dev_exportSnapshot` RPC and boots a
+//! node with an identical state through `--load-snapshot`, so that a bug report can ship the state
+//! it happened in. The loaded state becomes the genesis of the new chain; the blocks are kept for
+//! inspection only, as they cannot be imported on top of it.
+//!
+//! The file starts with [`MAGIC`], followed by the SCALE encoded [`VersionedSnapshot`].
+
+use std::{fs, path::Path};
+
+use codec::{Decode, Encode};
+use sc_client_api::{BlockBackend, StorageProvider};
+use sc_service::ChainSpec;
+use sp_blockchain::HeaderBackend;
+use sp_core::storage::{
+	well_known_keys::{is_child_storage_key, DEFAULT_CHILD_STORAGE_KEY_PREFIX},
+	ChildInfo, Storage, StorageChild, StorageKey,
+};
+use sp_runtime::traits::Header;
+use swanky_runtime::{opaque::Block, BlockNumber, Hash};
+
+/// Bytes every snapshot file starts with.
+pub const MAGIC: [u8; 8] = *b"swankysn";
+
+/// Key value pairs of a storage trie.
+type KeyValues = Vec<(Vec<u8>, Vec<u8>)>;
+
+/// Snapshot of every supported format version.
+#[derive(Encode, Decode)]
+pub enum VersionedSnapshot {
+	#[codec(index = 1)]
+	V1(SnapshotV1),
+}
+
+/// State at a block, with the blocks leading to it.
+#[derive(Debug, PartialEq, Eq, Encode, Decode)]
+pub struct SnapshotV1 {
+	/// Hash of the block the state is the one of.
+	pub block_hash: Hash,
+	/// Number of the block the state is the one of.
+	pub block_number: BlockNumber,
+	/// Key value pairs of the top trie, without the roots of child tries.
+	pub top: KeyValues,
+	/// Key value pairs of the default child tries, by storage key.
+	pub children: Vec<(Vec<u8>, KeyValues)>,
+	/// SCALE encoded blocks, oldest first and ending with the snapshot block.
+	pub blocks: Vec<Vec<u8>>,
+}
+
+impl SnapshotV1 {
+	/// Writes the snapshot to `path`, in the latest format.
+	pub fn write(self, path: &Path) -> Result<(), String> {
+		let data = [&MAGIC[..], &VersionedSnapshot::V1(self).encode()].concat();
+		fs::write(path, data).map_err(|e| format!("Unable to write {}: {}", path.display(), e))
+	}
+
+	/// Reads the snapshot at `path`, in any supported format.
+	pub fn read(path: &Path) -> Result<Self, String> {
+		let data =
+			fs::read(path).map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+		let snapshot = data
+			.strip_prefix(&MAGIC[..])
+			.ok_or_else(|| format!("{} is not a snapshot", path.display()))?;
+		let VersionedSnapshot::V1(snapshot) = VersionedSnapshot::decode(&mut &snapshot[..])
+			.map_err(|e| format!("Invalid snapshot {}: {}", path.display(), e))?;
+		Ok(snapshot)
+	}
+
+	/// Returns the state as the storage of a genesis.
+	pub fn into_storage(self) -> Storage {
+		let children_default = self
+			.children
+			.into_iter()
+			.map(|(storage_key, data)| {
+				let child_info = ChildInfo::new_default(&storage_key);
+				(storage_key, StorageChild { data: data.into_iter().collect(), child_info })
+			})
+			.collect();
+		let top = self.top.into_iter().filter(|(key, _)| !is_child_storage_key(key)).collect();
+
+		Storage { top, children_default }
+	}
+}
+
+/// Writes the state at block `at`, along with up to `blocks` blocks leading to it, to `path`.
+pub fn export<C, B>(client: &C, at: Hash, blocks: u32, path: &Path) -> Result<(), String>
+where
+	C: StorageProvider<Block, B> + BlockBackend<Block> + HeaderBackend<Block>,
+	B: sc_client_api::Backend<Block>,
+{
+	let header = client
+		.header(at)
+		.map_err(|e| e.to_string())?
+		.ok_or_else(|| format!("Unknown block {:?}", at))?;
+
+	let mut top = KeyValues::new();
+	let mut children = Vec::new();
+	for (key, value) in
+		client.storage_pairs(at, &StorageKey(Vec::new())).map_err(|e| e.to_string())?
+	{
+		let storage_key = match key.0.strip_prefix(DEFAULT_CHILD_STORAGE_KEY_PREFIX) {
+			Some(storage_key) => storage_key.to_vec(),
+			None => {
+				top.push((key.0, value.0));
+				continue
+			},
+		};
+
+		let child_info = ChildInfo::new_default(&storage_key);
+		let mut data = KeyValues::new();
+		for key in client
+			.child_storage_keys(at, &child_info, &StorageKey(Vec::new()))
+			.map_err(|e| e.to_string())?
+		{
+			if let Some(value) =
+				client.child_storage(at, &child_info, &key).map_err(|e| e.to_string())?
+			{
+				data.push((key.0, value.0));
+			}
+		}
+		children.push((storage_key, data));
+	}
+
+	let mut history = Vec::new();
+	let mut hash = at;
+	while history.len() < blocks as usize {
+		let block = match client.block(hash).map_err(|e| e.to_string())? {
+			Some(block) => block.block,
+			None => break,
+		};
+		hash = *block.header.parent_hash();
+		let genesis = block.header.number() == &0;
+		history.push(block.encode());
+		if genesis {
+			break
+		}
+	}
+	history.reverse();
+
+	SnapshotV1 { block_hash: at, block_number: *header.number(), top, children, blocks: history }
+		.write(path)
+}
+
+/// Replaces the genesis of `spec` with the state of the snapshot at `path`.
+pub fn load(spec: &mut dyn ChainSpec, path: &Path) -> Result<(), String> {
+	let snapshot = SnapshotV1::read(path)?;
+	log::info!(
+		"Loading the state of block #{} ({:?}), exported with {} blocks",
+		snapshot.block_number,
+		snapshot.block_hash,
+		snapshot.blocks.len()
+	);
+
+	spec.set_storage(snapshot.into_storage());
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::path::PathBuf;
+
+	/// Returns a path in the temporary directory, unique to the test `name`.
+	fn temp_path(name: &str) -> PathBuf {
+		std::env::temp_dir().join(format!("swanky-snapshot-{}-{}", std::process::id(), name))
+	}
+
+	fn snapshot() -> SnapshotV1 {
+		let child_root = [DEFAULT_CHILD_STORAGE_KEY_PREFIX, b"contract"].concat();
+		SnapshotV1 {
+			block_hash: Hash::repeat_byte(1),
+			block_number: 42,
+			top: vec![(b"key".to_vec(), b"value".to_vec()), (child_root, vec![0; 32])],
+			children: vec![(
+				b"contract".to_vec(),
+				vec![(b"child key".to_vec(), b"child".to_vec())],
+			)],
+			blocks: vec![vec![1, 2, 3]],
+		}
+	}
+
+	#[test]
+	fn snapshot_round_trips() {
+		let path = temp_path("round-trip");
+		snapshot().write(&path).unwrap();
+		let read = SnapshotV1::read(&path);
+		fs::remove_file(&path).unwrap();
+
+		assert_eq!(read.unwrap(), snapshot());
+	}
+
+	#[test]
+	fn other_files_are_rejected() {
+		let path = temp_path("not-a-snapshot");
+		fs::write(&path, b"{}").unwrap();
+		let not_a_snapshot = SnapshotV1::read(&path);
+		fs::write(&path, [&MAGIC[..], &[0]].concat()).unwrap();
+		let unknown_version = SnapshotV1::read(&path);
+		fs::remove_file(&path).unwrap();
+
+		assert!(not_a_snapshot.unwrap_err().contains("is not a snapshot"));
+		assert!(unknown_version.unwrap_err().contains("Invalid snapshot"));
+	}
+
+	#[test]
+	fn storage_holds_child_tries_apart() {
+		let storage = snapshot().into_storage();
+
+		assert_eq!(storage.top.len(), 1);
+		assert_eq!(storage.top.get(&b"key"[..]), Some(&b"value".to_vec()));
+		let child = &storage.children_default[&b"contract"[..]];
+		assert_eq!(child.child_info, ChildInfo::new_default(b"contract"));
+		assert_eq!(child.data.get(&b"child key"[..]), Some(&b"child".to_vec()));
+	}
+}
@@ -0,0 +1,123 @@
+//! # Asset Rate Pallet
+//!
+//! Conversion rates from the native token to `pallet_assets` assets, used to charge transaction
+//! fees in assets.
+//!
+//! A rate is the amount of asset units charged for one unit of the native token, so a fee of
+//! `fee` native units costs `rate * fee` asset units. Rates are set up in the chain spec and
+//! maintained by root through [`Pallet::set_rate`] and [`Pallet::remove_rate`]. Fees cannot be
+//! paid in an asset without a rate.
+//!
+//! The pallet implements [`ConversionToAssetBalance`] to be used with
+//! `pallet_asset_tx_payment::FungiblesAdapter`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::traits::tokens::ConversionToAssetBalance;
+use sp_runtime::{FixedPointNumber, FixedPointOperand, FixedU128};
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Identifier of the assets rates are kept for.
+		type AssetId: Parameter + Member + MaxEncodedLen + Copy + MaybeSerializeDeserialize;
+		/// Balance type shared by the native token and the assets.
+		type Balance: Parameter + Member + MaxEncodedLen + Copy + FixedPointOperand;
+	}
+
+	/// Asset units charged for one unit of the native token, keyed by asset.
+	#[pallet::storage]
+	#[pallet::getter(fn rate)]
+	pub type Rates<T: Config> = StorageMap<_, Blake2_128Concat, T::AssetId, FixedU128>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The rate of an asset was set.
+		RateSet { asset_id: T::AssetId, rate: FixedU128 },
+		/// The rate of an asset was removed.
+		RateRemoved { asset_id: T::AssetId },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// No rate is set for the asset.
+		UnknownRate,
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub rates: Vec<(T::AssetId, FixedU128)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { rates: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (asset_id, rate) in &self.rates {
+				Rates::<T>::insert(asset_id, rate);
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the rate of `asset_id`, replacing the previous one.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_rate(
+			origin: OriginFor<T>,
+			asset_id: T::AssetId,
+			rate: FixedU128,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Rates::<T>::insert(asset_id, rate);
+			Self::deposit_event(Event::RateSet { asset_id, rate });
+			Ok(())
+		}
+
+		/// Remove the rate of `asset_id`, so that fees can no longer be paid in it.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn remove_rate(origin: OriginFor<T>, asset_id: T::AssetId) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Rates::<T>::remove(asset_id);
+			Self::deposit_event(Event::RateRemoved { asset_id });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> ConversionToAssetBalance<T::Balance, T::AssetId, T::Balance> for Pallet<T> {
+	type Error = Error<T>;
+
+	fn to_asset_balance(balance: T::Balance, asset_id: T::AssetId) -> Result<T::Balance, Error<T>> {
+		let rate = Rates::<T>::get(asset_id).ok_or(Error::<T>::UnknownRate)?;
+		Ok(rate.saturating_mul_int(balance))
+	}
+}
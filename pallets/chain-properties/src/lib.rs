@@ -0,0 +1,60 @@
+//! # Chain Properties Pallet
+//!
+//! Chain properties the runtime reads from storage instead of compile time constants, so that they
+//! can be set in the chain spec.
+//!
+//! The SS58 prefix of the chain defaults to the one of the runtime profile and is set at genesis.
+//! The pallet implements `Get<u16>`, to be used as the `SS58Prefix` of `frame_system`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::traits::Get;
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// SS58 prefix used unless another one is set at genesis.
+		type DefaultSs58Prefix: Get<u16>;
+	}
+
+	/// SS58 prefix of the chain.
+	#[pallet::storage]
+	#[pallet::getter(fn ss58_prefix)]
+	pub type Ss58Prefix<T: Config> = StorageValue<_, u16, ValueQuery, T::DefaultSs58Prefix>;
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		pub ss58_prefix: Option<u16>,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self { ss58_prefix: None }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			if let Some(ss58_prefix) = self.ss58_prefix {
+				Ss58Prefix::<T>::put(ss58_prefix);
+			}
+		}
+	}
+}
+
+impl<T: Config> Get<u16> for Pallet<T> {
+	fn get() -> u16 {
+		Ss58Prefix::<T>::get()
+	}
+}
@@ -0,0 +1,192 @@
+//! # Contracts Call Filter Pallet
+//!
+//! A storage backed allowlist for the calls contracts may dispatch through `seal_call_runtime`.
+//!
+//! Entries are [`CallPattern`]s, naming either a single call such as `Balances::transfer` or
+//! every call of a pallet such as `Assets::*`, using the pallet names of `construct_runtime!`.
+//! The allowlist is set up in the chain spec and maintained by root through [`Pallet::allow`] and
+//! [`Pallet::disallow`], so that contracts can be tested under the exact allowlist of the chain
+//! they target. With an empty allowlist no call is allowed at all.
+//!
+//! The pallet implements [`Contains`] to be used as `pallet_contracts::Config::CallFilter`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	dispatch::{CallMetadata, GetCallMetadata},
+	traits::{ConstU32, Contains},
+	BoundedVec, RuntimeDebug,
+};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+/// Maximum length of a pallet or call name.
+pub const MAX_NAME_LEN: u32 = 64;
+
+/// Name of a pallet or call.
+pub type Name = BoundedVec<u8, ConstU32<MAX_NAME_LEN>>;
+
+/// Calls matched by an allowlist entry.
+///
+/// It is written `Pallet::call` for a single call, or `Pallet::*` for every call of a pallet.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct CallPattern {
+	/// Name of the pallet in `construct_runtime!`.
+	pub pallet: Name,
+	/// Name of the call, or `None` for every call of the pallet.
+	pub call: Option<Name>,
+}
+
+impl CallPattern {
+	/// Returns the patterns matching the call described by `metadata`, if it can be described.
+	fn matching(metadata: CallMetadata) -> Option<[CallPattern; 2]> {
+		let pallet = Name::try_from(metadata.pallet_name.as_bytes().to_vec()).ok()?;
+		let call = Name::try_from(metadata.function_name.as_bytes().to_vec()).ok()?;
+
+		Some([
+			CallPattern { pallet: pallet.clone(), call: None },
+			CallPattern { pallet, call: Some(call) },
+		])
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::fmt::Display for CallPattern {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		let call = self.call.as_ref().map_or("*".into(), |call| String::from_utf8_lossy(call));
+		write!(f, "{}::{}", String::from_utf8_lossy(&self.pallet), call)
+	}
+}
+
+#[cfg(feature = "std")]
+impl std::str::FromStr for CallPattern {
+	type Err = String;
+
+	fn from_str(s: &str) -> Result<Self, Self::Err> {
+		let name = |name: &str| {
+			Name::try_from(name.as_bytes().to_vec()).map_err(|_| format!("`{}` is too long", name))
+		};
+
+		match s.split_once("::") {
+			Some((pallet, "*")) => Ok(CallPattern { pallet: name(pallet)?, call: None }),
+			Some((pallet, call)) =>
+				Ok(CallPattern { pallet: name(pallet)?, call: Some(name(call)?) }),
+			None => Err(format!("`{}` is not of the form `Pallet::call` or `Pallet::*`", s)),
+		}
+	}
+}
+
+#[cfg(feature = "std")]
+impl Serialize for CallPattern {
+	fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+		serializer.serialize_str(&self.to_string())
+	}
+}
+
+#[cfg(feature = "std")]
+impl<'de> Deserialize<'de> for CallPattern {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		String::deserialize(deserializer)?.parse().map_err(serde::de::Error::custom)
+	}
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// The call type contracts dispatch.
+		type RuntimeCall: GetCallMetadata;
+	}
+
+	/// Entries of the allowlist.
+	#[pallet::storage]
+	pub type AllowedCalls<T: Config> = StorageMap<_, Blake2_128Concat, CallPattern, ()>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Calls matching the pattern were allowed.
+		Allowed { pattern: CallPattern },
+		/// Calls matching the pattern are no longer allowed.
+		Disallowed { pattern: CallPattern },
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		pub allowed: Vec<CallPattern>,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self { allowed: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			for pattern in &self.allowed {
+				AllowedCalls::<T>::insert(pattern, ());
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Allow contracts to dispatch the calls matching `pattern`.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn allow(origin: OriginFor<T>, pattern: CallPattern) -> DispatchResult {
+			ensure_root(origin)?;
+
+			AllowedCalls::<T>::insert(&pattern, ());
+			Self::deposit_event(Event::Allowed { pattern });
+			Ok(())
+		}
+
+		/// Remove `pattern` from the allowlist.
+		///
+		/// Calls matched by another entry, e.g. `Assets::*` for `Assets::transfer`, stay allowed.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn disallow(origin: OriginFor<T>, pattern: CallPattern) -> DispatchResult {
+			ensure_root(origin)?;
+
+			AllowedCalls::<T>::remove(&pattern);
+			Self::deposit_event(Event::Disallowed { pattern });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Contains<<T as Config>::RuntimeCall> for Pallet<T> {
+	fn contains(call: &<T as Config>::RuntimeCall) -> bool {
+		CallPattern::matching(call.get_call_metadata()).map_or(false, |patterns| {
+			patterns.iter().any(|pattern| AllowedCalls::<T>::contains_key(pattern))
+		})
+	}
+}
@@ -0,0 +1,114 @@
+use crate::{mock::*, AllowedCalls, CallPattern, Event, MAX_NAME_LEN};
+use frame_support::{assert_noop, assert_ok, traits::Contains};
+use sp_runtime::traits::BadOrigin;
+
+fn pattern(pattern: &str) -> CallPattern {
+	pattern.parse().unwrap()
+}
+
+fn remark() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn remark_with_event() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark_with_event { remark: vec![] })
+}
+
+#[test]
+fn parses_call_patterns() {
+	let call = pattern("Balances::transfer");
+	assert_eq!(&call.pallet[..], b"Balances");
+	assert_eq!(call.call.as_deref(), Some(&b"transfer"[..]));
+
+	let pallet = pattern("Assets::*");
+	assert_eq!(&pallet.pallet[..], b"Assets");
+	assert_eq!(pallet.call, None);
+}
+
+#[test]
+fn rejects_invalid_call_patterns() {
+	assert!("Balances".parse::<CallPattern>().is_err());
+	assert!("Balances:transfer".parse::<CallPattern>().is_err());
+
+	let too_long = "a".repeat(MAX_NAME_LEN as usize + 1);
+	assert!(format!("{}::*", too_long).parse::<CallPattern>().is_err());
+	assert!(format!("Balances::{}", too_long).parse::<CallPattern>().is_err());
+}
+
+#[test]
+fn call_patterns_round_trip() {
+	for text in ["Balances::transfer", "Assets::*"] {
+		let parsed = pattern(text);
+		assert_eq!(parsed.to_string(), text);
+
+		let json = serde_json::to_string(&parsed).unwrap();
+		assert_eq!(json, format!("\"{}\"", text));
+		assert_eq!(serde_json::from_str::<CallPattern>(&json).unwrap(), parsed);
+	}
+
+	assert!(serde_json::from_str::<CallPattern>("\"Balances\"").is_err());
+}
+
+#[test]
+fn empty_allowlist_allows_nothing() {
+	new_test_ext(&[]).execute_with(|| {
+		assert!(!ContractsCallFilter::contains(&remark()));
+	});
+}
+
+#[test]
+fn pallet_pattern_allows_every_call_of_the_pallet() {
+	new_test_ext(&["System::*"]).execute_with(|| {
+		assert!(ContractsCallFilter::contains(&remark()));
+		assert!(ContractsCallFilter::contains(&remark_with_event()));
+
+		let other = RuntimeCall::ContractsCallFilter(crate::Call::allow {
+			pattern: pattern("System::remark"),
+		});
+		assert!(!ContractsCallFilter::contains(&other));
+	});
+}
+
+#[test]
+fn call_pattern_allows_only_that_call() {
+	new_test_ext(&["System::remark"]).execute_with(|| {
+		assert!(ContractsCallFilter::contains(&remark()));
+		assert!(!ContractsCallFilter::contains(&remark_with_event()));
+	});
+}
+
+#[test]
+fn allow_and_disallow_update_the_allowlist() {
+	new_test_ext(&[]).execute_with(|| {
+		assert_ok!(ContractsCallFilter::allow(RuntimeOrigin::root(), pattern("System::remark")));
+		assert!(ContractsCallFilter::contains(&remark()));
+		System::assert_last_event(Event::Allowed { pattern: pattern("System::remark") }.into());
+
+		assert_ok!(ContractsCallFilter::disallow(RuntimeOrigin::root(), pattern("System::remark")));
+		assert!(!ContractsCallFilter::contains(&remark()));
+		assert!(!AllowedCalls::<Test>::contains_key(pattern("System::remark")));
+		System::assert_last_event(Event::Disallowed { pattern: pattern("System::remark") }.into());
+	});
+}
+
+#[test]
+fn disallowing_a_call_keeps_the_pallet_pattern() {
+	new_test_ext(&["System::*", "System::remark"]).execute_with(|| {
+		assert_ok!(ContractsCallFilter::disallow(RuntimeOrigin::root(), pattern("System::remark")));
+		assert!(ContractsCallFilter::contains(&remark()));
+	});
+}
+
+#[test]
+fn allowlist_is_maintained_by_root() {
+	new_test_ext(&[]).execute_with(|| {
+		assert_noop!(
+			ContractsCallFilter::allow(RuntimeOrigin::signed(1), pattern("System::*")),
+			BadOrigin
+		);
+		assert_noop!(
+			ContractsCallFilter::disallow(RuntimeOrigin::signed(1), pattern("System::*")),
+			BadOrigin
+		);
+	});
+}
@@ -0,0 +1,211 @@
+//! # Dev Oracle Pallet
+//!
+//! Mock price feeds for local contract development.
+//!
+//! Feeds are keyed by a symbol or by a `pallet_assets` asset ID and hold the latest value
+//! together with the time it was set. Values are set by root, either immediately through
+//! [`Pallet::set_price`] or ahead of time through [`Pallet::schedule_prices`], which lets a test
+//! script how a price evolves over the next blocks. Scheduled values are applied at the start of
+//! their block, so their timestamp is the one of the parent block.
+//!
+//! The pallet does not interpret values in any way: their unit and precision are up to the
+//! contracts consuming them.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{traits::ConstU32, BoundedVec, RuntimeDebug};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+
+pub use pallet::*;
+
+/// Maximum length of a feed symbol.
+pub const MAX_SYMBOL_LEN: u32 = 16;
+
+/// Symbol of a feed, e.g. `b"DOT"`.
+pub type Symbol = BoundedVec<u8, ConstU32<MAX_SYMBOL_LEN>>;
+
+/// Identifies a price feed.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub enum FeedKey<AssetId> {
+	/// Feed of a well known symbol.
+	Symbol(Symbol),
+	/// Feed of a `pallet_assets` asset.
+	Asset(AssetId),
+}
+
+/// Latest value of a feed.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen, RuntimeDebug, TypeInfo)]
+pub struct PriceData<Price, Moment> {
+	/// The value itself.
+	pub value: Price,
+	/// Time at which the value was set.
+	pub updated_at: Moment,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, traits::Time};
+	use frame_system::pallet_prelude::*;
+	use sp_std::prelude::*;
+
+	pub type FeedKeyOf<T> = FeedKey<<T as Config>::AssetId>;
+	pub type MomentOf<T> = <<T as Config>::Time as Time>::Moment;
+	pub type PriceDataOf<T> = PriceData<<T as Config>::Price, MomentOf<T>>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Identifier of the assets feeds can be kept for.
+		type AssetId: Parameter + Member + MaxEncodedLen + Copy + MaybeSerializeDeserialize;
+		/// Type of the values held by feeds.
+		type Price: Parameter + Member + MaxEncodedLen + Copy + MaybeSerializeDeserialize;
+		/// Time source used to stamp updates.
+		type Time: Time;
+		/// Maximum number of updates that can be scheduled for a single block.
+		#[pallet::constant]
+		type MaxUpdatesPerBlock: Get<u32>;
+	}
+
+	/// Latest value of every feed.
+	#[pallet::storage]
+	#[pallet::getter(fn price)]
+	pub type Prices<T: Config> = StorageMap<_, Blake2_128Concat, FeedKeyOf<T>, PriceDataOf<T>>;
+
+	/// Updates to apply at the start of a block.
+	#[pallet::storage]
+	pub type ScheduledPrices<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::BlockNumber,
+		BoundedVec<(FeedKeyOf<T>, T::Price), T::MaxUpdatesPerBlock>,
+		ValueQuery,
+	>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The value of a feed was updated.
+		PriceSet { key: FeedKeyOf<T>, value: T::Price },
+		/// A feed was removed.
+		PriceRemoved { key: FeedKeyOf<T> },
+		/// An update of a feed was scheduled.
+		PriceScheduled { key: FeedKeyOf<T>, at: T::BlockNumber, value: T::Price },
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// Updates can only be scheduled for future blocks.
+		ScheduleInPast,
+		/// Too many updates are already scheduled for the block.
+		TooManyUpdates,
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub prices: Vec<(FeedKeyOf<T>, T::Price)>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { prices: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for (key, value) in &self.prices {
+				Prices::<T>::insert(key, PriceData { value: *value, updated_at: T::Time::now() });
+			}
+		}
+	}
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let updates = ScheduledPrices::<T>::take(now);
+			let count = updates.len() as u64;
+
+			for (key, value) in updates {
+				Self::do_set_price(key, value);
+			}
+
+			T::DbWeight::get().reads_writes(1 + count, 1 + count)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the value of a feed, creating it if needed.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(1, 1))]
+		pub fn set_price(
+			origin: OriginFor<T>,
+			key: FeedKeyOf<T>,
+			value: T::Price,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Self::do_set_price(key, value);
+			Ok(())
+		}
+
+		/// Remove a feed. Its scheduled updates are kept and recreate it when applied.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn remove_price(origin: OriginFor<T>, key: FeedKeyOf<T>) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Prices::<T>::remove(&key);
+			Self::deposit_event(Event::PriceRemoved { key });
+			Ok(())
+		}
+
+		/// Schedule updates of a feed, each given as the block to apply it at and the value.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::call_index(2)]
+		#[pallet::weight(T::DbWeight::get().reads_writes(updates.len() as u64, updates.len() as u64))]
+		pub fn schedule_prices(
+			origin: OriginFor<T>,
+			key: FeedKeyOf<T>,
+			updates: BoundedVec<(T::BlockNumber, T::Price), T::MaxUpdatesPerBlock>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			let now = frame_system::Pallet::<T>::block_number();
+			for (at, value) in updates {
+				ensure!(at > now, Error::<T>::ScheduleInPast);
+
+				ScheduledPrices::<T>::try_mutate(at, |scheduled| {
+					scheduled.try_push((key.clone(), value)).map_err(|_| Error::<T>::TooManyUpdates)
+				})?;
+				Self::deposit_event(Event::PriceScheduled { key: key.clone(), at, value });
+			}
+
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		fn do_set_price(key: FeedKeyOf<T>, value: T::Price) {
+			Prices::<T>::insert(&key, PriceData { value, updated_at: T::Time::now() });
+			Self::deposit_event(Event::PriceSet { key, value });
+		}
+	}
+}
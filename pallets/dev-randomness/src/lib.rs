@@ -0,0 +1,91 @@
+//! # Dev Randomness Pallet
+//!
+//! A deterministic [`Randomness`] source for local contract development.
+//!
+//! Random values are derived from a seed kept in storage, the requested subject and the current
+//! block number. Replaying the same transactions on top of the same seed therefore always yields
+//! the same values, which makes lottery and game contracts testable. The seed is configured at
+//! genesis and can be replaced at any time by root through [`Pallet::set_seed`].
+//!
+//! This pallet provides no security whatsoever and must never be used on a production chain.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use frame_support::traits::Randomness;
+use sp_runtime::traits::Hash;
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+	}
+
+	/// Seed all random values are derived from.
+	#[pallet::storage]
+	#[pallet::getter(fn seed)]
+	pub type Seed<T: Config> = StorageValue<_, T::Hash, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The randomness seed was replaced.
+		SeedSet { seed: T::Hash },
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub seed: T::Hash,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { seed: Default::default() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			Seed::<T>::put(self.seed);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Replace the seed random values are derived from.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_seed(origin: OriginFor<T>, seed: T::Hash) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Seed::<T>::put(seed);
+			Self::deposit_event(Event::SeedSet { seed });
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Randomness<T::Hash, T::BlockNumber> for Pallet<T> {
+	/// Derive a value from the seed, `subject` and the current block number.
+	///
+	/// The returned block number is the current one, as the value is known from its start.
+	fn random(subject: &[u8]) -> (T::Hash, T::BlockNumber) {
+		let block_number = frame_system::Pallet::<T>::block_number();
+		let random = T::Hashing::hash_of(&(Seed::<T>::get(), subject, block_number));
+
+		(random, block_number)
+	}
+}
@@ -0,0 +1,226 @@
+//! # Free Transactions Pallet
+//!
+//! A runtime switchable mode in which transactions are free.
+//!
+//! Contract tests often need to reason about exact balances, which fee deductions get in the way
+//! of. While the mode is enabled, [`ChargeAssetTxPayment`] waives the fee of every transaction,
+//! whether paid in the native token or in assets, so that even accounts with tiny balances can
+//! transact, and reports the fee that would have been charged in a [`Event::FeeWaived`] event
+//! instead of a fee paid event.
+//!
+//! The mode is set in the chain spec and switched by root through [`Pallet::set_enabled`].
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::dispatch::{DispatchInfo, DispatchResult, PostDispatchInfo};
+use pallet_asset_tx_payment::OnChargeAssetTransaction;
+use pallet_transaction_payment::OnChargeTransaction;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, PostDispatchInfoOf, SignedExtension, Zero},
+	transaction_validity::{TransactionValidity, TransactionValidityError, ValidTransaction},
+	FixedPointOperand,
+};
+
+pub use pallet::*;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+type BalanceOf<T> = <<T as pallet_transaction_payment::Config>::OnChargeTransaction as OnChargeTransaction<
+	T,
+>>::Balance;
+type ChargeAssetIdOf<T> = <<T as pallet_asset_tx_payment::Config>::OnChargeAssetTransaction as
+	OnChargeAssetTransaction<T>>::AssetId;
+type ChargeFeeOf<T> = pallet_asset_tx_payment::ChargeAssetTxPayment<T>;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_asset_tx_payment::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+	}
+
+	/// Whether transactions are free.
+	#[pallet::storage]
+	#[pallet::getter(fn enabled)]
+	pub type Enabled<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Free transactions were enabled or disabled.
+		EnabledSet { enabled: bool },
+		/// The fee of a transaction was waived. It is given in the native token, the tip included,
+		/// even if the transaction would have paid in an asset.
+		FeeWaived { who: T::AccountId, fee: BalanceOf<T>, tip: BalanceOf<T> },
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		pub enabled: bool,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self { enabled: false }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			Enabled::<T>::put(self.enabled);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Enable or disable free transactions.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_enabled(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Enabled::<T>::put(enabled);
+			Self::deposit_event(Event::EnabledSet { enabled });
+			Ok(())
+		}
+	}
+}
+
+/// Pays the fee of a transaction like `pallet_asset_tx_payment::ChargeAssetTxPayment`, unless
+/// free transactions are enabled.
+///
+/// It encodes like `ChargeAssetTxPayment` and has the same identifier, so that transactions are
+/// built and signed the same way. A transaction is free if the mode was enabled when it was
+/// dispatched: nothing is withdrawn, and [`Event::FeeWaived`] is emitted instead of
+/// `TransactionFeePaid` or `AssetTxFeePaid`.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct ChargeAssetTxPayment<T: Config> {
+	#[codec(compact)]
+	tip: BalanceOf<T>,
+	asset_id: Option<ChargeAssetIdOf<T>>,
+}
+
+impl<T: Config> ChargeAssetTxPayment<T> {
+	/// Utility constructor. Used only in client/factory code.
+	pub fn from(tip: BalanceOf<T>, asset_id: Option<ChargeAssetIdOf<T>>) -> Self {
+		Self { tip, asset_id }
+	}
+
+	fn charge_fee(&self) -> ChargeFeeOf<T> {
+		ChargeFeeOf::<T>::from(self.tip, self.asset_id)
+	}
+}
+
+impl<T: Config> sp_std::fmt::Debug for ChargeAssetTxPayment<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "ChargeAssetTxPayment<{:?}, {:?}>", self.tip, self.asset_id.encode())
+	}
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+/// The fee payment of a transaction, from `pre_dispatch` to `post_dispatch`.
+pub enum Payment<T: Config>
+where
+	ChargeFeeOf<T>: SignedExtension,
+{
+	/// The fee was withdrawn by `pallet_asset_tx_payment`.
+	Charged(<ChargeFeeOf<T> as SignedExtension>::Pre),
+	/// The fee was waived.
+	Waived { who: T::AccountId, tip: BalanceOf<T> },
+}
+
+impl<T: Config> SignedExtension for ChargeAssetTxPayment<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo, PostInfo = PostDispatchInfo>,
+	BalanceOf<T>: Send + Sync + From<u64> + FixedPointOperand,
+	ChargeAssetIdOf<T>: Send + Sync,
+	ChargeFeeOf<T>:
+		SignedExtension<AccountId = T::AccountId, Call = T::RuntimeCall, AdditionalSigned = ()>,
+{
+	const IDENTIFIER: &'static str = "ChargeAssetTxPayment";
+	type AccountId = T::AccountId;
+	type Call = T::RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = Payment<T>;
+
+	fn additional_signed(&self) -> Result<Self::AdditionalSigned, TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> TransactionValidity {
+		if Pallet::<T>::enabled() {
+			let priority = pallet_transaction_payment::ChargeTransactionPayment::<T>::get_priority(
+				info,
+				len,
+				self.tip,
+				Zero::zero(),
+			);
+			return Ok(ValidTransaction { priority, ..Default::default() })
+		}
+
+		self.charge_fee().validate(who, call, info, len)
+	}
+
+	fn pre_dispatch(
+		self,
+		who: &Self::AccountId,
+		call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		if Pallet::<T>::enabled() {
+			return Ok(Payment::Waived { who: who.clone(), tip: self.tip })
+		}
+
+		self.charge_fee().pre_dispatch(who, call, info, len).map(Payment::Charged)
+	}
+
+	fn post_dispatch(
+		pre: Option<Self::Pre>,
+		info: &DispatchInfoOf<Self::Call>,
+		post_info: &PostDispatchInfoOf<Self::Call>,
+		len: usize,
+		result: &DispatchResult,
+	) -> Result<(), TransactionValidityError> {
+		match pre {
+			Some(Payment::Waived { who, tip }) => {
+				let fee = pallet_transaction_payment::Pallet::<T>::compute_actual_fee(
+					len as u32, info, post_info, tip,
+				);
+				Pallet::<T>::deposit_event(Event::FeeWaived { who, fee, tip });
+				Ok(())
+			},
+			Some(Payment::Charged(pre)) =>
+				ChargeFeeOf::<T>::post_dispatch(Some(pre), info, post_info, len, result),
+			None => ChargeFeeOf::<T>::post_dispatch(None, info, post_info, len, result),
+		}
+	}
+}
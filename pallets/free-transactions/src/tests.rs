@@ -0,0 +1,136 @@
+use crate::{mock::*, ChargeAssetTxPayment, Event};
+use frame_support::{
+	assert_ok,
+	dispatch::{DispatchInfo, PostDispatchInfo},
+	weights::Weight,
+};
+use sp_runtime::traits::SignedExtension;
+
+const TIP: u64 = 10;
+const LEN: usize = 20;
+
+fn remark() -> RuntimeCall {
+	RuntimeCall::System(frame_system::Call::remark { remark: vec![] })
+}
+
+fn info() -> DispatchInfo {
+	DispatchInfo { weight: Weight::from_ref_time(5), ..Default::default() }
+}
+
+fn post_info() -> PostDispatchInfo {
+	PostDispatchInfo { actual_weight: None, pays_fee: Default::default() }
+}
+
+fn set_enabled(enabled: bool) {
+	assert_ok!(FreeTransactions::set_enabled(RuntimeOrigin::root(), enabled));
+}
+
+fn fee() -> u64 {
+	TransactionPayment::compute_actual_fee(LEN as u32, &info(), &post_info(), TIP)
+}
+
+/// Dispatches a remark by Alice, running `during` between `pre_dispatch` and `post_dispatch`.
+fn dispatch(asset_id: Option<u32>, during: impl FnOnce()) {
+	let pre = ChargeAssetTxPayment::<Test>::from(TIP, asset_id)
+		.pre_dispatch(&ALICE, &remark(), &info(), LEN)
+		.unwrap();
+	during();
+	assert_ok!(ChargeAssetTxPayment::<Test>::post_dispatch(
+		Some(pre),
+		&info(),
+		&post_info(),
+		LEN,
+		&Ok(())
+	));
+}
+
+fn fee_waived() -> bool {
+	System::events().iter().any(|record| {
+		record.event ==
+			RuntimeEvent::FreeTransactions(Event::FeeWaived { who: ALICE, fee: fee(), tip: TIP })
+	})
+}
+
+fn fee_paid() -> bool {
+	System::events().iter().any(|record| {
+		matches!(
+			record.event,
+			RuntimeEvent::TransactionPayment(
+				pallet_transaction_payment::Event::TransactionFeePaid { .. }
+			) | RuntimeEvent::AssetTxPayment(pallet_asset_tx_payment::Event::AssetTxFeePaid { .. })
+		)
+	})
+}
+
+#[test]
+fn charges_fees_while_disabled() {
+	new_test_ext().execute_with(|| {
+		dispatch(None, || ());
+
+		assert_eq!(Balances::free_balance(ALICE), BALANCE - fee());
+		assert!(fee_paid());
+		assert!(!fee_waived());
+	});
+}
+
+#[test]
+fn waives_fees_while_enabled() {
+	new_test_ext().execute_with(|| {
+		set_enabled(true);
+		dispatch(None, || ());
+
+		assert_eq!(Balances::free_balance(ALICE), BALANCE);
+		assert!(fee_waived());
+		assert!(!fee_paid());
+	});
+}
+
+#[test]
+fn waives_fees_in_assets_while_enabled() {
+	new_test_ext().execute_with(|| {
+		dispatch(Some(ASSET), || ());
+		assert!(Assets::balance(ASSET, ALICE) < BALANCE);
+		assert!(fee_paid());
+
+		System::reset_events();
+		let balance = Assets::balance(ASSET, ALICE);
+		set_enabled(true);
+		dispatch(Some(ASSET), || ());
+
+		assert_eq!(Assets::balance(ASSET, ALICE), balance);
+		assert_eq!(Balances::free_balance(ALICE), BALANCE);
+		assert!(fee_waived());
+		assert!(!fee_paid());
+	});
+}
+
+#[test]
+fn mode_at_dispatch_decides() {
+	new_test_ext().execute_with(|| {
+		set_enabled(true);
+		dispatch(None, || set_enabled(false));
+
+		assert_eq!(Balances::free_balance(ALICE), BALANCE);
+		assert!(fee_waived());
+		assert!(!fee_paid());
+
+		System::reset_events();
+		dispatch(None, || set_enabled(true));
+
+		assert_eq!(Balances::free_balance(ALICE), BALANCE - fee());
+		assert!(fee_paid());
+		assert!(!fee_waived());
+	});
+}
+
+#[test]
+fn accounts_without_funds_transact_while_enabled() {
+	new_test_ext().execute_with(|| {
+		let payment = ChargeAssetTxPayment::<Test>::from(0, None);
+		assert!(payment.validate(&BOB, &remark(), &info(), LEN).is_err());
+
+		set_enabled(true);
+		assert_ok!(payment.validate(&BOB, &remark(), &info(), LEN));
+		assert_ok!(payment.pre_dispatch(&BOB, &remark(), &info(), LEN));
+	});
+}
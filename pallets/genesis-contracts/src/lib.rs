@@ -0,0 +1,112 @@
+//! # Genesis Contracts Pallet
+//!
+//! Contracts instantiated at genesis, so that test fixtures exist from block 0 on.
+//!
+//! Each contract of the [`GenesisConfig`] is uploaded and instantiated by its deployer, in the
+//! order they are listed, just like with `Contracts::instantiate_with_code`. The address of a
+//! contract only depends on its deployer, code and salt, so it is the same on every chain created
+//! from the same chain spec. The genesis build panics if a contract cannot be instantiated, which
+//! [`Pallet::instantiate`] allows to check beforehand.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::vec::Vec;
+
+pub use pallet::*;
+
+/// Log target of the pallet.
+const LOG_TARGET: &str = "runtime::genesis-contracts";
+
+/// A contract instantiated at genesis.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct GenesisContract<AccountId, Balance> {
+	/// Account uploading and instantiating the contract, paying its deposits.
+	pub deployer: AccountId,
+	/// Wasm code of the contract.
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+	pub code: Vec<u8>,
+	/// Input of the constructor, i.e. its selector followed by the SCALE encoded arguments.
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+	pub data: Vec<u8>,
+	/// Salt the address of the contract is derived from.
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+	pub salt: Vec<u8>,
+	/// Balance transferred from the deployer to the contract.
+	pub value: Balance,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, traits::Currency};
+	use pallet_contracts_primitives::Code;
+
+	type BalanceOf<T> = <<T as pallet_contracts::Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::Balance;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_contracts::Config {}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub contracts: Vec<GenesisContract<T::AccountId, BalanceOf<T>>>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { contracts: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for contract in &self.contracts {
+				let account_id = Pallet::<T>::instantiate(contract)
+					.expect("genesis contract can be instantiated");
+
+				log::info!(target: LOG_TARGET, "Instantiated genesis contract at {:?}", account_id);
+			}
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Uploads and instantiates `contract` like the genesis build, returning its address.
+		///
+		/// Allows to dry-run genesis contracts on top of the rest of the genesis state, as the
+		/// genesis build panics if a contract fails.
+		pub fn instantiate(
+			contract: &GenesisContract<T::AccountId, BalanceOf<T>>,
+		) -> Result<T::AccountId, DispatchError> {
+			let gas_limit = <T as frame_system::Config>::BlockWeights::get().max_block;
+
+			let result = pallet_contracts::Pallet::<T>::bare_instantiate(
+				contract.deployer.clone(),
+				contract.value,
+				gas_limit,
+				None,
+				Code::Upload(contract.code.clone()),
+				contract.data.clone(),
+				contract.salt.clone(),
+				false,
+			)
+			.result?;
+			if result.result.did_revert() {
+				return Err(DispatchError::Other("constructor reverted"))
+			}
+			Ok(result.account_id)
+		}
+	}
+}
@@ -0,0 +1,142 @@
+//! # Genesis Uniques Pallet
+//!
+//! `pallet_uniques` collections and items created at genesis, as `pallet_uniques` has no genesis
+//! config of its own.
+//!
+//! Collections are force created with free holding, so neither their owner nor the item owners
+//! pay deposits, and each item is minted by the owner of its collection. Metadata is set when
+//! given, without deposit either. The genesis build panics if any of this fails, e.g. on a
+//! duplicate ID or on metadata longer than the string limit, so the config is to be validated
+//! beforehand.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::vec::Vec;
+
+pub use pallet::*;
+
+/// A collection created at genesis.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct GenesisCollection<CollectionId, ItemId, AccountId> {
+	/// ID of the collection.
+	pub id: CollectionId,
+	/// Owner, issuer, admin and freezer of the collection.
+	pub owner: AccountId,
+	/// Metadata of the collection, none if empty.
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+	pub metadata: Vec<u8>,
+	/// Items minted in the collection.
+	pub items: Vec<GenesisItem<ItemId, AccountId>>,
+}
+
+/// An item minted at genesis.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct GenesisItem<ItemId, AccountId> {
+	/// ID of the item within its collection.
+	pub id: ItemId,
+	/// Owner of the item.
+	pub owner: AccountId,
+	/// Metadata of the item, none if empty.
+	#[cfg_attr(feature = "std", serde(with = "sp_core::bytes"))]
+	pub metadata: Vec<u8>,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::{pallet_prelude::*, sp_runtime::traits::StaticLookup};
+	use frame_system::RawOrigin;
+
+	type UniquesOf<T> = pallet_uniques::Pallet<T>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config + pallet_uniques::Config {
+		/// Collection ID in the genesis config, converted into the one of `pallet_uniques`.
+		type CollectionId: Member
+			+ Parameter
+			+ MaybeSerializeDeserialize
+			+ Into<<Self as pallet_uniques::Config>::CollectionId>;
+		/// Item ID in the genesis config, converted into the one of `pallet_uniques`.
+		type ItemId: Member
+			+ Parameter
+			+ MaybeSerializeDeserialize
+			+ Into<<Self as pallet_uniques::Config>::ItemId>;
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		pub collections: Vec<
+			GenesisCollection<<T as Config>::CollectionId, <T as Config>::ItemId, T::AccountId>,
+		>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { collections: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			for collection in &self.collections {
+				let id: <T as pallet_uniques::Config>::CollectionId = collection.id.clone().into();
+				UniquesOf::<T>::force_create(
+					RawOrigin::Root.into(),
+					id,
+					T::Lookup::unlookup(collection.owner.clone()),
+					true,
+				)
+				.expect("genesis collection can be created");
+				if !collection.metadata.is_empty() {
+					UniquesOf::<T>::set_collection_metadata(
+						RawOrigin::Root.into(),
+						id,
+						bounded::<T>(&collection.metadata),
+						false,
+					)
+					.expect("genesis collection metadata can be set");
+				}
+
+				for item in &collection.items {
+					let item_id: <T as pallet_uniques::Config>::ItemId = item.id.clone().into();
+					UniquesOf::<T>::mint(
+						RawOrigin::Signed(collection.owner.clone()).into(),
+						id,
+						item_id,
+						T::Lookup::unlookup(item.owner.clone()),
+					)
+					.expect("genesis item can be minted");
+					if !item.metadata.is_empty() {
+						UniquesOf::<T>::set_metadata(
+							RawOrigin::Root.into(),
+							id,
+							item_id,
+							bounded::<T>(&item.metadata),
+							false,
+						)
+						.expect("genesis item metadata can be set");
+					}
+				}
+			}
+		}
+	}
+
+	/// Bounds genesis metadata to the string limit of `pallet_uniques`.
+	fn bounded<T: Config>(metadata: &[u8]) -> BoundedVec<u8, T::StringLimit> {
+		metadata.to_vec().try_into().expect("genesis metadata fits the string limit")
+	}
+}
@@ -0,0 +1,110 @@
+//! # Mock Chain Extension Pallet
+//!
+//! Canned chain extension responses for local contract development.
+//!
+//! Contracts written for other chains often call chain extensions this node does not implement.
+//! Root can register a response for any pair of extension ID and function ID through
+//! [`Pallet::set_response`]. The runtime answers matching calls with the registered output bytes
+//! and return code instead of trapping, so such contracts can still be tested.
+//!
+//! Registered responses take precedence over the chain extensions implemented by the runtime.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::Get, BoundedVec, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use scale_info::TypeInfo;
+
+pub use pallet::*;
+
+/// Response returned to a contract calling a mocked chain extension function.
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	Encode,
+	Decode,
+	MaxEncodedLen,
+	RuntimeDebugNoBound,
+	TypeInfo,
+)]
+#[scale_info(skip_type_params(MaxOutputLen))]
+#[codec(mel_bound())]
+pub struct MockResponse<MaxOutputLen: Get<u32>> {
+	/// Bytes written to the output buffer of the contract.
+	pub output: BoundedVec<u8, MaxOutputLen>,
+	/// Value returned by `seal_call_chain_extension`.
+	pub return_code: u32,
+}
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Maximum length of the output of a response.
+		#[pallet::constant]
+		type MaxOutputLen: Get<u32>;
+	}
+
+	/// Responses, keyed by extension ID and function ID.
+	#[pallet::storage]
+	#[pallet::getter(fn response)]
+	pub type Responses<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, u16, Twox64Concat, u16, MockResponse<T::MaxOutputLen>>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// A response was registered.
+		ResponseSet { ext_id: u16, func_id: u16, return_code: u32 },
+		/// A response was removed.
+		ResponseRemoved { ext_id: u16, func_id: u16 },
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Register the response to calls of `func_id` of the extension `ext_id`, replacing the
+		/// previous one.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_response(
+			origin: OriginFor<T>,
+			ext_id: u16,
+			func_id: u16,
+			output: BoundedVec<u8, T::MaxOutputLen>,
+			return_code: u32,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Responses::<T>::insert(ext_id, func_id, MockResponse { output, return_code });
+			Self::deposit_event(Event::ResponseSet { ext_id, func_id, return_code });
+			Ok(())
+		}
+
+		/// Remove the response to calls of `func_id` of the extension `ext_id`.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::call_index(1)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn remove_response(origin: OriginFor<T>, ext_id: u16, func_id: u16) -> DispatchResult {
+			ensure_root(origin)?;
+
+			Responses::<T>::remove(ext_id, func_id);
+			Self::deposit_event(Event::ResponseRemoved { ext_id, func_id });
+			Ok(())
+		}
+	}
+}
@@ -0,0 +1,192 @@
+//! # PoV Limit Pallet
+//!
+//! Root configurable proof size limits, to mimic the proof of validity (PoV) limits of parachains.
+//!
+//! A standalone chain has no use for proof sizes, so this node does not limit them by default.
+//! The limits set here, in the chain spec or by root through [`Pallet::set_limits`], are enforced
+//! by the [`CheckProofSize`] signed extension, leaving the block weights of the runtime constant.
+//! Transactions declaring a proof size above the extrinsic limit, e.g. contract calls with a larger
+//! `gas_limit`, are then rejected and blocks are not filled beyond the block limit.
+//!
+//! These limits only apply to declared proof sizes. The node measures the proof size of the blocks
+//! it builds separately.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use frame_support::dispatch::DispatchInfo;
+use scale_info::TypeInfo;
+use sp_runtime::{
+	traits::{DispatchInfoOf, Dispatchable, SignedExtension},
+	transaction_validity::{
+		InvalidTransaction, TransactionValidity, TransactionValidityError, ValidTransaction,
+	},
+};
+use sp_std::marker::PhantomData;
+
+pub use pallet::*;
+
+#[frame_support::pallet]
+pub mod pallet {
+	use super::*;
+	use frame_support::pallet_prelude::*;
+	use frame_system::pallet_prelude::*;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+	}
+
+	/// Maximum proof size of a block, unlimited if unset.
+	#[pallet::storage]
+	#[pallet::getter(fn max_block_proof_size)]
+	pub type MaxBlockProofSize<T: Config> = StorageValue<_, u64>;
+
+	/// Maximum proof size of a single extrinsic, unlimited if unset.
+	#[pallet::storage]
+	#[pallet::getter(fn max_extrinsic_proof_size)]
+	pub type MaxExtrinsicProofSize<T: Config> = StorageValue<_, u64>;
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(super) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The proof size limits were set.
+		LimitsSet { max_block_proof_size: Option<u64>, max_extrinsic_proof_size: Option<u64> },
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig {
+		pub max_block_proof_size: Option<u64>,
+		pub max_extrinsic_proof_size: Option<u64>,
+	}
+
+	#[cfg(feature = "std")]
+	impl Default for GenesisConfig {
+		fn default() -> Self {
+			Self { max_block_proof_size: None, max_extrinsic_proof_size: None }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig {
+		fn build(&self) {
+			MaxBlockProofSize::<T>::set(self.max_block_proof_size);
+			MaxExtrinsicProofSize::<T>::set(self.max_extrinsic_proof_size);
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the proof size limits of blocks and extrinsics, `None` meaning unlimited.
+		///
+		/// The dispatch origin for this call must be _Root_.
+		#[pallet::call_index(0)]
+		#[pallet::weight(T::DbWeight::get().writes(2))]
+		pub fn set_limits(
+			origin: OriginFor<T>,
+			max_block_proof_size: Option<u64>,
+			max_extrinsic_proof_size: Option<u64>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			MaxBlockProofSize::<T>::set(max_block_proof_size);
+			MaxExtrinsicProofSize::<T>::set(max_extrinsic_proof_size);
+			Self::deposit_event(Event::LimitsSet {
+				max_block_proof_size,
+				max_extrinsic_proof_size,
+			});
+			Ok(())
+		}
+	}
+}
+
+/// Rejects transactions declaring a proof size above the limits of [`Pallet`].
+///
+/// A transaction is rejected if its proof size exceeds the extrinsic limit, or if it does not fit
+/// in the proof size left in the block. It must come before `frame_system::CheckWeight`, which
+/// adds the weight of the transaction to the block.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, TypeInfo)]
+#[scale_info(skip_type_params(T))]
+pub struct CheckProofSize<T: Config + Send + Sync>(PhantomData<T>);
+
+impl<T: Config + Send + Sync> CheckProofSize<T> {
+	/// Creates new `SignedExtension` to check the proof size of transactions.
+	pub fn new() -> Self {
+		Self(PhantomData)
+	}
+
+	/// Checks the proof size declared by `info` against the limits.
+	fn check(info: &DispatchInfo) -> Result<(), TransactionValidityError> {
+		let proof_size = info.weight.proof_size();
+		if MaxExtrinsicProofSize::<T>::get().map_or(false, |limit| proof_size > limit) {
+			return Err(InvalidTransaction::ExhaustsResources.into())
+		}
+
+		if let Some(limit) = MaxBlockProofSize::<T>::get() {
+			let used = frame_system::Pallet::<T>::block_weight().total().proof_size();
+			if used.saturating_add(proof_size) > limit {
+				return Err(InvalidTransaction::ExhaustsResources.into())
+			}
+		}
+
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> Default for CheckProofSize<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<T: Config + Send + Sync> sp_std::fmt::Debug for CheckProofSize<T> {
+	#[cfg(feature = "std")]
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		write!(f, "CheckProofSize")
+	}
+
+	#[cfg(not(feature = "std"))]
+	fn fmt(&self, _: &mut sp_std::fmt::Formatter) -> sp_std::fmt::Result {
+		Ok(())
+	}
+}
+
+impl<T: Config + Send + Sync> SignedExtension for CheckProofSize<T>
+where
+	T::RuntimeCall: Dispatchable<Info = DispatchInfo>,
+{
+	const IDENTIFIER: &'static str = "CheckProofSize";
+	type AccountId = T::AccountId;
+	type Call = T::RuntimeCall;
+	type AdditionalSigned = ();
+	type Pre = ();
+
+	fn additional_signed(&self) -> Result<(), TransactionValidityError> {
+		Ok(())
+	}
+
+	fn validate(
+		&self,
+		_who: &Self::AccountId,
+		_call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> TransactionValidity {
+		Self::check(info)?;
+		Ok(ValidTransaction::default())
+	}
+
+	fn pre_dispatch(
+		self,
+		_who: &Self::AccountId,
+		_call: &Self::Call,
+		info: &DispatchInfoOf<Self::Call>,
+		_len: usize,
+	) -> Result<Self::Pre, TransactionValidityError> {
+		Self::check(info)
+	}
+}
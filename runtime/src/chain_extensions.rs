@@ -1,12 +1,165 @@
+//! Chain extensions available to WASM contracts.
 //!
+//! Every extension is registered under an ID, which makes up the upper 16 bits of the function ID
+//! passed to `seal_call_chain_extension`, i.e. `#[ink(extension = (ID << 16) | func_id)]`.
+//!
+//! | ID       | Extension       |
+//! |----------|-----------------|
+//! | `0x0000` | dApps staking   |
+//! | `0x0001` | Reserved (XVM)  |
+//! | upstream | Assets          |
+//! | `0x0003` | Uniques         |
+//! | `0x0004` | Balances        |
+//! | `0x0005` | Randomness      |
+//! | `0x0006` | Oracle          |
+//!
+//! The assets extension keeps the ID `pallet-assets-chain-extension` registers itself under, which
+//! `obce` derives from its definition and the upstream ink! bindings use.
+//!
+//! The same information is available at runtime through [`ChainExtensionsApi`].
+//!
+//! All of them are wrapped into [`MockableExtension`], which lets root register canned responses
+//! for any extension and function ID, including the ones not implemented by this runtime.
 use super::Runtime;
+use codec::{Decode, Encode};
 /// Registered WASM contracts chain extensions.
 use pallet_contracts::chain_extension::RegisteredChainExtension;
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_runtime::{DispatchError, ModuleError, RuntimeDebug, RuntimeString};
+use sp_std::prelude::*;
 
+pub use assets::AssetsExtension;
+pub use balances::BalancesExtension;
+pub use mock::MockableExtension;
+pub use oracle::OracleExtension;
 pub use pallet_chain_extension_dapps_staking::DappsStakingExtension;
+pub use randomness::RandomnessExtension;
+pub use uniques::UniquesExtension;
+
+mod assets;
+mod balances;
+mod mock;
+mod oracle;
+mod randomness;
+mod uniques;
 
 // Following impls defines chain extension IDs.
 
 impl RegisteredChainExtension<Runtime> for DappsStakingExtension<Runtime> {
 	const ID: u16 = 0x0000;
 }
+
+impl RegisteredChainExtension<Runtime> for AssetsExtension {
+	const ID: u16 =
+		<pallet_assets_chain_extension::substrate::AssetsExtension as RegisteredChainExtension<
+			Runtime,
+		>>::ID;
+}
+
+impl RegisteredChainExtension<Runtime> for UniquesExtension {
+	const ID: u16 = 0x0003;
+}
+
+impl RegisteredChainExtension<Runtime> for BalancesExtension {
+	const ID: u16 = 0x0004;
+}
+
+impl RegisteredChainExtension<Runtime> for RandomnessExtension {
+	const ID: u16 = 0x0005;
+}
+
+impl RegisteredChainExtension<Runtime> for OracleExtension {
+	const ID: u16 = 0x0006;
+}
+
+/// Function IDs and names of `pallet-chain-extension-dapps-staking`.
+const DAPPS_STAKING_FUNCTIONS: &[(u16, &str)] = &[
+	(1, "current_era"),
+	(2, "unbonding_period"),
+	(3, "era_rewards"),
+	(4, "era_staked"),
+	(5, "staked_amount"),
+	(6, "staked_amount_on_contract"),
+	(7, "read_contract_stake"),
+	(8, "bond_and_stake"),
+	(9, "unbond_and_unstake"),
+	(10, "withdraw_unbonded"),
+	(11, "claim_staker"),
+	(12, "claim_dapp"),
+	(13, "set_reward_destination"),
+	(14, "nomination_transfer"),
+];
+
+/// A function of a chain extension.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ChainExtensionFunction {
+	/// ID of the function within its extension.
+	pub func_id: u16,
+	/// Full ID of the function, as used in `#[ink(extension = ..)]`.
+	pub id: u32,
+	/// Name of the function.
+	pub name: RuntimeString,
+}
+
+/// A chain extension registered in the runtime.
+#[derive(Clone, PartialEq, Eq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ChainExtensionInfo {
+	/// ID the extension is registered under.
+	pub id: u16,
+	/// Name of the extension.
+	pub name: RuntimeString,
+	/// Functions of the extension.
+	pub functions: Vec<ChainExtensionFunction>,
+}
+
+sp_api::decl_runtime_apis! {
+	/// API to discover the chain extensions available to contracts.
+	pub trait ChainExtensionsApi {
+		/// Returns every registered chain extension with its functions.
+		fn chain_extensions() -> Vec<ChainExtensionInfo>;
+	}
+}
+
+/// Returns every chain extension of `pallet_contracts::Config::ChainExtension`.
+pub fn registered() -> Vec<ChainExtensionInfo> {
+	vec![
+		info::<DappsStakingExtension<Runtime>>("dapps_staking", DAPPS_STAKING_FUNCTIONS),
+		info::<AssetsExtension>("assets", &assets::functions()),
+		info::<UniquesExtension>("uniques", uniques::FUNCTIONS),
+		info::<BalancesExtension>("balances", balances::FUNCTIONS),
+		info::<RandomnessExtension>("randomness", randomness::FUNCTIONS),
+		info::<OracleExtension>("oracle", oracle::FUNCTIONS),
+	]
+}
+
+fn info<E: RegisteredChainExtension<Runtime>>(
+	name: &'static str,
+	functions: &[(u16, &'static str)],
+) -> ChainExtensionInfo {
+	let functions = functions
+		.iter()
+		.map(|&(func_id, name)| ChainExtensionFunction {
+			func_id,
+			id: (E::ID as u32) << 16 | func_id as u32,
+			name: RuntimeString::Borrowed(name),
+		})
+		.collect();
+
+	ChainExtensionInfo { id: E::ID, name: RuntimeString::Borrowed(name), functions }
+}
+
+/// Returns the name of the pallet error carried by `error`, if any.
+///
+/// Chain extensions use it to map dispatch errors onto their own return codes.
+fn module_error_message(error: DispatchError) -> Option<&'static str> {
+	match error {
+		DispatchError::Module(ModuleError { message, .. }) => message,
+		_ => None,
+	}
+}
@@ -0,0 +1,86 @@
+//! Chain extension exposing `pallet_assets` to WASM contracts.
+//!
+//! The extension itself is implemented by `pallet-assets-chain-extension`, and keeps the ID it is
+//! registered under upstream, so that contracts built against its ink! bindings work unchanged.
+//! This wrapper only lists its functions for the chain extensions runtime API.
+
+use crate::Runtime;
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use sp_core::hashing::blake2_256;
+use sp_runtime::DispatchError;
+use sp_std::prelude::*;
+
+/// Functions defined by `pallet-assets-chain-extension`.
+const FUNCTION_NAMES: &[&str] = &[
+	"create",
+	"mint",
+	"burn",
+	"balance_of",
+	"total_supply",
+	"allowance",
+	"approve_transfer",
+	"transfer",
+	"transfer_approved",
+	"set_metadata",
+	"metadata_name",
+	"metadata_symbol",
+	"metadata_decimals",
+];
+
+/// Function IDs and names, as listed by the chain extensions runtime API.
+///
+/// `pallet-assets-chain-extension` is defined with `obce`, which derives the ID of a function from
+/// its name: the first two bytes of its BLAKE2b-256 hash, big-endian.
+pub(super) fn functions() -> Vec<(u16, &'static str)> {
+	FUNCTION_NAMES
+		.iter()
+		.map(|&name| {
+			let hash = blake2_256(name.as_bytes());
+			(u16::from_be_bytes([hash[0], hash[1]]), name)
+		})
+		.collect()
+}
+
+/// Chain extension giving contracts access to `pallet_assets`.
+#[derive(Default)]
+pub struct AssetsExtension(pallet_assets_chain_extension::substrate::AssetsExtension);
+
+impl ChainExtension<Runtime> for AssetsExtension {
+	fn call<E>(&mut self, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = Runtime>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		ChainExtension::<Runtime>::call(&mut self.0, env)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use blake2::{digest::consts::U32, Blake2b, Digest};
+
+	/// Derives the ID of a method the way `obce-codegen` does, from the BLAKE2b-256 hash of its
+	/// identifier.
+	fn obce_method_id(name: &str) -> u16 {
+		let hash = Blake2b::<U32>::digest(name.as_bytes());
+		u16::from_be_bytes([hash[0], hash[1]])
+	}
+
+	#[test]
+	fn function_ids_match_obce() {
+		for (id, name) in functions() {
+			assert_eq!(id, obce_method_id(name), "{}", name);
+		}
+	}
+
+	#[test]
+	fn function_ids_are_unique() {
+		let mut ids = functions().into_iter().map(|(id, _)| id).collect::<Vec<_>>();
+		ids.sort_unstable();
+		ids.dedup();
+		assert_eq!(ids.len(), FUNCTION_NAMES.len());
+	}
+}
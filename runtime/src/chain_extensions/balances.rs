@@ -0,0 +1,250 @@
+//! Chain extension exposing the native currency (`pallet_balances`) to WASM contracts.
+//!
+//! Balance lookups accept any account, while transfers, reserves and locks always act on the
+//! calling contract's own account. State changing functions report their outcome through the
+//! returned [`BalancesError`] code.
+
+use super::module_error_message;
+use crate::{AccountId, Balance, Balances, Runtime};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::traits::{
+	Currency, ExistenceRequirement, Get, LockIdentifier, LockableCurrency, ReservableCurrency,
+	WithdrawReasons,
+};
+use pallet_balances::WeightInfo;
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use sp_runtime::{DispatchError, DispatchResult};
+
+/// Functions exposed by [`BalancesExtension`], keyed by their function ID.
+enum BalancesFunc {
+	TotalBalance,
+	FreeBalance,
+	ReservedBalance,
+	FrozenBalance,
+	TotalIssuance,
+	MinimumBalance,
+	Transfer,
+	TransferKeepAlive,
+	Reserve,
+	Unreserve,
+	SetLock,
+	RemoveLock,
+}
+
+impl TryFrom<u16> for BalancesFunc {
+	type Error = DispatchError;
+
+	fn try_from(value: u16) -> Result<Self, Self::Error> {
+		match value {
+			1 => Ok(BalancesFunc::TotalBalance),
+			2 => Ok(BalancesFunc::FreeBalance),
+			3 => Ok(BalancesFunc::ReservedBalance),
+			4 => Ok(BalancesFunc::FrozenBalance),
+			5 => Ok(BalancesFunc::TotalIssuance),
+			6 => Ok(BalancesFunc::MinimumBalance),
+			7 => Ok(BalancesFunc::Transfer),
+			8 => Ok(BalancesFunc::TransferKeepAlive),
+			9 => Ok(BalancesFunc::Reserve),
+			10 => Ok(BalancesFunc::Unreserve),
+			11 => Ok(BalancesFunc::SetLock),
+			12 => Ok(BalancesFunc::RemoveLock),
+			_ => Err(DispatchError::Other("BalancesExtension: Unimplemented func_id")),
+		}
+	}
+}
+
+/// Function IDs and names of [`BalancesFunc`], as listed by the chain extensions runtime API.
+pub(super) const FUNCTIONS: &[(u16, &str)] = &[
+	(1, "total_balance"),
+	(2, "free_balance"),
+	(3, "reserved_balance"),
+	(4, "frozen_balance"),
+	(5, "total_issuance"),
+	(6, "minimum_balance"),
+	(7, "transfer"),
+	(8, "transfer_keep_alive"),
+	(9, "reserve"),
+	(10, "unreserve"),
+	(11, "set_lock"),
+	(12, "remove_lock"),
+];
+
+/// Return codes of [`BalancesExtension`].
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, Debug)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+#[repr(u32)]
+pub enum BalancesError {
+	/// Success
+	Success = 0,
+	/// Vesting balance too high to send value.
+	VestingBalance = 1,
+	/// Account liquidity restrictions prevent withdrawal.
+	LiquidityRestrictions = 2,
+	/// Balance too low to send value.
+	InsufficientBalance = 3,
+	/// Value too low to create account due to existential deposit.
+	ExistentialDeposit = 4,
+	/// Transfer/payment would kill account.
+	KeepAlive = 5,
+	/// Beneficiary account must pre-exist.
+	DeadAccount = 6,
+	/// Number of named reserves exceed `MaxReserves`.
+	TooManyReserves = 7,
+	/// Unknown error
+	UnknownError = 99,
+}
+
+impl From<DispatchError> for BalancesError {
+	fn from(error: DispatchError) -> Self {
+		match module_error_message(error) {
+			Some("VestingBalance") => BalancesError::VestingBalance,
+			Some("LiquidityRestrictions") => BalancesError::LiquidityRestrictions,
+			Some("InsufficientBalance") => BalancesError::InsufficientBalance,
+			Some("ExistentialDeposit") => BalancesError::ExistentialDeposit,
+			Some("KeepAlive") => BalancesError::KeepAlive,
+			Some("DeadAccount") => BalancesError::DeadAccount,
+			Some("TooManyReserves") => BalancesError::TooManyReserves,
+			_ => BalancesError::UnknownError,
+		}
+	}
+}
+
+impl From<DispatchResult> for BalancesError {
+	fn from(result: DispatchResult) -> Self {
+		result.map_or_else(Into::into, |_| BalancesError::Success)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen)]
+pub struct TransferInput {
+	pub to: AccountId,
+	pub value: Balance,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen)]
+pub struct SetLockInput {
+	pub id: LockIdentifier,
+	pub value: Balance,
+}
+
+/// Chain extension giving contracts access to the native currency.
+#[derive(Default)]
+pub struct BalancesExtension;
+
+impl ChainExtension<Runtime> for BalancesExtension {
+	fn call<E>(&mut self, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = Runtime>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let func_id = env.func_id().try_into()?;
+		let mut env = env.buf_in_buf_out();
+		let db_weight = <Runtime as frame_system::Config>::DbWeight::get();
+
+		let result = match func_id {
+			BalancesFunc::TotalBalance => {
+				let who: AccountId = env.read_as()?;
+				env.charge_weight(db_weight.reads(1))?;
+
+				env.write(&Balances::total_balance(&who).encode(), false, None)?;
+				Ok(())
+			},
+			BalancesFunc::FreeBalance => {
+				let who: AccountId = env.read_as()?;
+				env.charge_weight(db_weight.reads(1))?;
+
+				env.write(&Balances::free_balance(&who).encode(), false, None)?;
+				Ok(())
+			},
+			BalancesFunc::ReservedBalance => {
+				let who: AccountId = env.read_as()?;
+				env.charge_weight(db_weight.reads(1))?;
+
+				env.write(&Balances::reserved_balance(&who).encode(), false, None)?;
+				Ok(())
+			},
+			BalancesFunc::FrozenBalance => {
+				let who: AccountId = env.read_as()?;
+				env.charge_weight(db_weight.reads(1))?;
+
+				let account = frame_system::Pallet::<Runtime>::account(&who).data;
+				let frozen: Balance = account.misc_frozen.max(account.fee_frozen);
+				env.write(&frozen.encode(), false, None)?;
+				Ok(())
+			},
+			BalancesFunc::TotalIssuance => {
+				env.charge_weight(db_weight.reads(1))?;
+
+				env.write(&Balances::total_issuance().encode(), false, None)?;
+				Ok(())
+			},
+			BalancesFunc::MinimumBalance => {
+				env.write(&Balances::minimum_balance().encode(), false, None)?;
+				Ok(())
+			},
+			BalancesFunc::Transfer => {
+				let input: TransferInput = env.read_as()?;
+				env.charge_weight(<Runtime as pallet_balances::Config>::WeightInfo::transfer())?;
+				let from = env.ext().address().clone();
+
+				<Balances as Currency<AccountId>>::transfer(
+					&from,
+					&input.to,
+					input.value,
+					ExistenceRequirement::AllowDeath,
+				)
+			},
+			BalancesFunc::TransferKeepAlive => {
+				let input: TransferInput = env.read_as()?;
+				env.charge_weight(
+					<Runtime as pallet_balances::Config>::WeightInfo::transfer_keep_alive(),
+				)?;
+				let from = env.ext().address().clone();
+
+				<Balances as Currency<AccountId>>::transfer(
+					&from,
+					&input.to,
+					input.value,
+					ExistenceRequirement::KeepAlive,
+				)
+			},
+			BalancesFunc::Reserve => {
+				let value: Balance = env.read_as()?;
+				env.charge_weight(db_weight.reads_writes(1, 1))?;
+				let who = env.ext().address().clone();
+
+				Balances::reserve(&who, value)
+			},
+			BalancesFunc::Unreserve => {
+				let value: Balance = env.read_as()?;
+				env.charge_weight(db_weight.reads_writes(1, 1))?;
+				let who = env.ext().address().clone();
+
+				// The amount that could not be unreserved is handed back to the contract.
+				let remaining = Balances::unreserve(&who, value);
+				env.write(&remaining.encode(), false, None)?;
+				Ok(())
+			},
+			BalancesFunc::SetLock => {
+				let input: SetLockInput = env.read_as()?;
+				env.charge_weight(db_weight.reads_writes(2, 2))?;
+				let who = env.ext().address().clone();
+
+				Balances::set_lock(input.id, &who, input.value, WithdrawReasons::all());
+				Ok(())
+			},
+			BalancesFunc::RemoveLock => {
+				let id: LockIdentifier = env.read_as()?;
+				env.charge_weight(db_weight.reads_writes(2, 2))?;
+				let who = env.ext().address().clone();
+
+				Balances::remove_lock(id, &who);
+				Ok(())
+			},
+		};
+
+		Ok(RetVal::Converging(BalancesError::from(result) as u32))
+	}
+}
@@ -0,0 +1,34 @@
+//! Chain extension answering calls with the responses registered in
+//! `pallet_mock_chain_extension`.
+
+use crate::{MockChainExtension, Runtime};
+use frame_support::traits::Get;
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use sp_runtime::DispatchError;
+
+/// Wraps the chain extensions of the runtime, answering the calls a response is registered for
+/// and forwarding every other call to `E`.
+#[derive(Default)]
+pub struct MockableExtension<E>(E);
+
+impl<E: ChainExtension<Runtime>> ChainExtension<Runtime> for MockableExtension<E> {
+	fn call<Ex>(&mut self, mut env: Environment<Ex, InitState>) -> Result<RetVal, DispatchError>
+	where
+		Ex: Ext<T = Runtime>,
+		<Ex::T as SysConfig>::AccountId: UncheckedFrom<<Ex::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		env.charge_weight(<Runtime as frame_system::Config>::DbWeight::get().reads(1))?;
+
+		match MockChainExtension::response(env.ext_id(), env.func_id()) {
+			Some(response) => {
+				let mut env = env.buf_in_buf_out();
+				env.write(&response.output, false, None)?;
+
+				Ok(RetVal::Converging(response.return_code))
+			},
+			None => self.0.call(env),
+		}
+	}
+}
@@ -0,0 +1,58 @@
+//! Chain extension exposing the `pallet_dev_oracle` price feeds to WASM contracts.
+
+use crate::{DevOracle, Runtime};
+use codec::Encode;
+use frame_support::traits::Get;
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use pallet_dev_oracle::FeedKeyOf;
+use sp_runtime::DispatchError;
+
+/// Functions exposed by [`OracleExtension`], keyed by their function ID.
+enum OracleFunc {
+	LatestPrice,
+}
+
+impl TryFrom<u16> for OracleFunc {
+	type Error = DispatchError;
+
+	fn try_from(value: u16) -> Result<Self, Self::Error> {
+		match value {
+			1 => Ok(OracleFunc::LatestPrice),
+			_ => Err(DispatchError::Other("OracleExtension: Unimplemented func_id")),
+		}
+	}
+}
+
+/// Function IDs and names of [`OracleFunc`], as listed by the chain extensions runtime API.
+pub(super) const FUNCTIONS: &[(u16, &str)] = &[(1, "latest_price")];
+
+/// Chain extension giving contracts read access to the mock price feeds.
+///
+/// `LatestPrice` takes a `FeedKey` and returns the SCALE encoded `Option<PriceData>`, holding the
+/// latest value and the timestamp it was set at.
+#[derive(Default)]
+pub struct OracleExtension;
+
+impl ChainExtension<Runtime> for OracleExtension {
+	fn call<E>(&mut self, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = Runtime>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let func_id = env.func_id().try_into()?;
+		let mut env = env.buf_in_buf_out();
+
+		match func_id {
+			OracleFunc::LatestPrice => {
+				let key: FeedKeyOf<Runtime> = env.read_as()?;
+				env.charge_weight(<Runtime as frame_system::Config>::DbWeight::get().reads(1))?;
+
+				env.write(&DevOracle::price(key).encode(), false, None)?;
+			},
+		}
+
+		Ok(RetVal::Converging(0))
+	}
+}
@@ -0,0 +1,64 @@
+//! Chain extension exposing the `pallet_dev_randomness` source to WASM contracts.
+
+use crate::{DevRandomness, Runtime};
+use codec::Encode;
+use frame_support::traits::{Get, Randomness};
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use sp_runtime::DispatchError;
+use sp_std::prelude::*;
+
+/// Functions exposed by [`RandomnessExtension`], keyed by their function ID.
+enum RandomnessFunc {
+	Random,
+	Seed,
+}
+
+impl TryFrom<u16> for RandomnessFunc {
+	type Error = DispatchError;
+
+	fn try_from(value: u16) -> Result<Self, Self::Error> {
+		match value {
+			1 => Ok(RandomnessFunc::Random),
+			2 => Ok(RandomnessFunc::Seed),
+			_ => Err(DispatchError::Other("RandomnessExtension: Unimplemented func_id")),
+		}
+	}
+}
+
+/// Function IDs and names of [`RandomnessFunc`], as listed by the chain extensions runtime API.
+pub(super) const FUNCTIONS: &[(u16, &str)] = &[(1, "random"), (2, "seed")];
+
+/// Chain extension giving contracts access to the seedable randomness source.
+///
+/// `Random` takes a subject of arbitrary length and returns the SCALE encoded
+/// `(Hash, BlockNumber)` pair, just like `seal_random`. `Seed` returns the seed in use.
+#[derive(Default)]
+pub struct RandomnessExtension;
+
+impl ChainExtension<Runtime> for RandomnessExtension {
+	fn call<E>(&mut self, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = Runtime>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let func_id = env.func_id().try_into()?;
+		let mut env = env.buf_in_buf_out();
+		env.charge_weight(<Runtime as frame_system::Config>::DbWeight::get().reads(1))?;
+
+		match func_id {
+			RandomnessFunc::Random => {
+				let len = env.in_len();
+				let subject: Vec<u8> = env.read_as_unbounded(len)?;
+
+				env.write(&DevRandomness::random(&subject).encode(), false, None)?;
+			},
+			RandomnessFunc::Seed => {
+				env.write(&DevRandomness::seed().encode(), false, None)?;
+			},
+		}
+
+		Ok(RetVal::Converging(0))
+	}
+}
@@ -0,0 +1,419 @@
+//! Chain extension exposing `pallet_uniques` NFTs to WASM contracts.
+//!
+//! State changing functions are dispatched with the calling contract as the signed origin and
+//! report their outcome through the returned [`UniquesError`] code. Read queries write their
+//! SCALE encoded result into the output buffer and always return [`UniquesError::Success`].
+
+use super::module_error_message;
+use crate::{AccountId, Balance, Runtime};
+use codec::{Compact, Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	traits::{tokens::nonfungibles::Inspect, Get},
+	Blake2_128Concat, BoundedVec,
+};
+use frame_system::RawOrigin;
+use pallet_contracts::chain_extension::{
+	ChainExtension, Environment, Ext, InitState, RetVal, SysConfig, UncheckedFrom,
+};
+use pallet_uniques::WeightInfo;
+use sp_runtime::{traits::StaticLookup, DispatchError, DispatchResult};
+use sp_std::prelude::*;
+
+type CollectionId = <Runtime as pallet_uniques::Config>::CollectionId;
+type ItemId = <Runtime as pallet_uniques::Config>::ItemId;
+type Lookup = <Runtime as frame_system::Config>::Lookup;
+type Uniques = pallet_uniques::Pallet<Runtime>;
+
+/// Functions exposed by [`UniquesExtension`], keyed by their function ID.
+enum UniquesFunc {
+	CreateCollection,
+	Mint,
+	Burn,
+	Transfer,
+	Approve,
+	SetAttribute,
+	Owner,
+	Attribute,
+	CollectionDetails,
+}
+
+impl TryFrom<u16> for UniquesFunc {
+	type Error = DispatchError;
+
+	fn try_from(value: u16) -> Result<Self, Self::Error> {
+		match value {
+			1 => Ok(UniquesFunc::CreateCollection),
+			2 => Ok(UniquesFunc::Mint),
+			3 => Ok(UniquesFunc::Burn),
+			4 => Ok(UniquesFunc::Transfer),
+			5 => Ok(UniquesFunc::Approve),
+			6 => Ok(UniquesFunc::SetAttribute),
+			7 => Ok(UniquesFunc::Owner),
+			8 => Ok(UniquesFunc::Attribute),
+			9 => Ok(UniquesFunc::CollectionDetails),
+			_ => Err(DispatchError::Other("UniquesExtension: Unimplemented func_id")),
+		}
+	}
+}
+
+/// Function IDs and names of [`UniquesFunc`], as listed by the chain extensions runtime API.
+pub(super) const FUNCTIONS: &[(u16, &str)] = &[
+	(1, "create_collection"),
+	(2, "mint"),
+	(3, "burn"),
+	(4, "transfer"),
+	(5, "approve"),
+	(6, "set_attribute"),
+	(7, "owner"),
+	(8, "attribute"),
+	(9, "collection_details"),
+];
+
+/// Return codes of [`UniquesExtension`].
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, Debug)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+#[repr(u32)]
+pub enum UniquesError {
+	/// Success
+	Success = 0,
+	/// The signing account has no permission to do the operation.
+	NoPermission = 1,
+	/// The given collection ID is unknown.
+	UnknownCollection = 2,
+	/// The item ID has already been used for an item.
+	AlreadyExists = 3,
+	/// The owner turned out to be different to what was expected.
+	WrongOwner = 4,
+	/// The item ID is already taken.
+	InUse = 5,
+	/// The item or collection is frozen.
+	Frozen = 6,
+	/// The delegate turned out to be different to what was expected.
+	WrongDelegate = 7,
+	/// There is no delegate approved.
+	NoDelegate = 8,
+	/// No approval exists that would allow the transfer.
+	Unapproved = 9,
+	/// The item is locked.
+	Locked = 10,
+	/// The given item ID is unknown.
+	UnknownItem = 11,
+	/// The contract cannot cover the required deposit.
+	InsufficientBalance = 12,
+	/// An attribute key or value exceeds the configured limit.
+	LimitExceeded = 13,
+	/// Unknown error
+	UnknownError = 99,
+}
+
+impl From<DispatchError> for UniquesError {
+	fn from(error: DispatchError) -> Self {
+		match module_error_message(error) {
+			Some("NoPermission") => UniquesError::NoPermission,
+			Some("UnknownCollection") => UniquesError::UnknownCollection,
+			Some("AlreadyExists") => UniquesError::AlreadyExists,
+			Some("WrongOwner") => UniquesError::WrongOwner,
+			Some("InUse") => UniquesError::InUse,
+			Some("Frozen") => UniquesError::Frozen,
+			Some("WrongDelegate") => UniquesError::WrongDelegate,
+			Some("NoDelegate") => UniquesError::NoDelegate,
+			Some("Unapproved") => UniquesError::Unapproved,
+			Some("Locked") => UniquesError::Locked,
+			Some("UnknownItem") => UniquesError::UnknownItem,
+			Some("InsufficientBalance") => UniquesError::InsufficientBalance,
+			_ => UniquesError::UnknownError,
+		}
+	}
+}
+
+impl From<DispatchResult> for UniquesError {
+	fn from(result: DispatchResult) -> Self {
+		result.map_or_else(Into::into, |_| UniquesError::Success)
+	}
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen)]
+pub struct CreateCollectionInput {
+	pub collection: CollectionId,
+	pub admin: AccountId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen)]
+pub struct ItemAccountInput {
+	pub collection: CollectionId,
+	pub item: ItemId,
+	pub account: AccountId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode, MaxEncodedLen)]
+pub struct ItemInput {
+	pub collection: CollectionId,
+	pub item: ItemId,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct SetAttributeInput {
+	pub collection: CollectionId,
+	pub maybe_item: Option<ItemId>,
+	pub key: Vec<u8>,
+	pub value: Vec<u8>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct AttributeInput {
+	pub collection: CollectionId,
+	pub maybe_item: Option<ItemId>,
+	pub key: Vec<u8>,
+}
+
+impl AttributeInput {
+	/// Encoded length of the longest input with a key within the limit of `pallet_uniques`.
+	fn max_len() -> u32 {
+		let fields = CollectionId::max_encoded_len() +
+			Option::<ItemId>::max_encoded_len() +
+			Compact::<u32>::max_encoded_len();
+		fields as u32 + <Runtime as pallet_uniques::Config>::KeyLimit::get()
+	}
+}
+
+impl SetAttributeInput {
+	/// Encoded length of the longest input with a key and a value within the limits of
+	/// `pallet_uniques`.
+	fn max_len() -> u32 {
+		AttributeInput::max_len() +
+			Compact::<u32>::max_encoded_len() as u32 +
+			<Runtime as pallet_uniques::Config>::ValueLimit::get()
+	}
+}
+
+/// Summary of a collection returned by [`UniquesFunc::CollectionDetails`].
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+pub struct CollectionDetails {
+	pub owner: AccountId,
+	pub items: u32,
+}
+
+/// Details of a collection as stored by `pallet_uniques`, whose own type keeps its fields private.
+///
+/// Every field is listed to mirror the stored layout, even though only some are read. The public
+/// API of `pallet_uniques` only counts the items of a collection by iterating over them, while the
+/// stored details keep count. A test checks the layout against collections the pallet creates.
+#[allow(dead_code)]
+#[derive(Encode, Decode)]
+struct StoredCollection {
+	owner: AccountId,
+	issuer: AccountId,
+	admin: AccountId,
+	freezer: AccountId,
+	total_deposit: Balance,
+	free_holding: bool,
+	items: u32,
+	item_metadatas: u32,
+	attributes: u32,
+	is_frozen: bool,
+}
+
+/// The `Collection` storage of `pallet_uniques`, stored under its former `Class` name.
+#[frame_support::storage_alias]
+type Class = StorageMap<Uniques, Blake2_128Concat, CollectionId, StoredCollection>;
+
+/// Chain extension giving contracts access to `pallet_uniques`.
+#[derive(Default)]
+pub struct UniquesExtension;
+
+impl ChainExtension<Runtime> for UniquesExtension {
+	fn call<E>(&mut self, env: Environment<E, InitState>) -> Result<RetVal, DispatchError>
+	where
+		E: Ext<T = Runtime>,
+		<E::T as SysConfig>::AccountId: UncheckedFrom<<E::T as SysConfig>::Hash> + AsRef<[u8]>,
+	{
+		let func_id = env.func_id().try_into()?;
+		let mut env = env.buf_in_buf_out();
+		let read_weight = <Runtime as frame_system::Config>::DbWeight::get().reads(1);
+
+		let result = match func_id {
+			UniquesFunc::CreateCollection => {
+				let input: CreateCollectionInput = env.read_as()?;
+				env.charge_weight(<Runtime as pallet_uniques::Config>::WeightInfo::create())?;
+				let origin = RawOrigin::Signed(env.ext().address().clone());
+
+				Uniques::create(origin.into(), input.collection, Lookup::unlookup(input.admin))
+			},
+			UniquesFunc::Mint => {
+				let input: ItemAccountInput = env.read_as()?;
+				env.charge_weight(<Runtime as pallet_uniques::Config>::WeightInfo::mint())?;
+				let origin = RawOrigin::Signed(env.ext().address().clone());
+
+				Uniques::mint(
+					origin.into(),
+					input.collection,
+					input.item,
+					Lookup::unlookup(input.account),
+				)
+			},
+			UniquesFunc::Burn => {
+				let input: ItemInput = env.read_as()?;
+				env.charge_weight(<Runtime as pallet_uniques::Config>::WeightInfo::burn())?;
+				let origin = RawOrigin::Signed(env.ext().address().clone());
+
+				Uniques::burn(origin.into(), input.collection, input.item, None)
+			},
+			UniquesFunc::Transfer => {
+				let input: ItemAccountInput = env.read_as()?;
+				env.charge_weight(<Runtime as pallet_uniques::Config>::WeightInfo::transfer())?;
+				let origin = RawOrigin::Signed(env.ext().address().clone());
+
+				Uniques::transfer(
+					origin.into(),
+					input.collection,
+					input.item,
+					Lookup::unlookup(input.account),
+				)
+			},
+			UniquesFunc::Approve => {
+				let input: ItemAccountInput = env.read_as()?;
+				env.charge_weight(
+					<Runtime as pallet_uniques::Config>::WeightInfo::approve_transfer(),
+				)?;
+				let origin = RawOrigin::Signed(env.ext().address().clone());
+
+				Uniques::approve_transfer(
+					origin.into(),
+					input.collection,
+					input.item,
+					Lookup::unlookup(input.account),
+				)
+			},
+			UniquesFunc::SetAttribute => {
+				env.charge_weight(
+					<Runtime as pallet_uniques::Config>::WeightInfo::set_attribute(),
+				)?;
+				let len = env.in_len();
+				if len > SetAttributeInput::max_len() {
+					return Ok(RetVal::Converging(UniquesError::LimitExceeded as u32))
+				}
+				let input: SetAttributeInput = env.read_as_unbounded(len)?;
+
+				let (key, value) =
+					match (BoundedVec::try_from(input.key), BoundedVec::try_from(input.value)) {
+						(Ok(key), Ok(value)) => (key, value),
+						_ => return Ok(RetVal::Converging(UniquesError::LimitExceeded as u32)),
+					};
+				let origin = RawOrigin::Signed(env.ext().address().clone());
+
+				Uniques::set_attribute(
+					origin.into(),
+					input.collection,
+					input.maybe_item,
+					key,
+					value,
+				)
+			},
+			UniquesFunc::Owner => {
+				let input: ItemInput = env.read_as()?;
+				env.charge_weight(read_weight)?;
+
+				let owner: Option<AccountId> = Uniques::owner(input.collection, input.item);
+				env.write(&owner.encode(), false, None)?;
+				Ok(())
+			},
+			UniquesFunc::Attribute => {
+				env.charge_weight(read_weight)?;
+				let len = env.in_len();
+				if len > AttributeInput::max_len() {
+					return Ok(RetVal::Converging(UniquesError::LimitExceeded as u32))
+				}
+				let input: AttributeInput = env.read_as_unbounded(len)?;
+
+				let value = match input.maybe_item {
+					Some(item) => <Uniques as Inspect<AccountId>>::attribute(
+						&input.collection,
+						&item,
+						&input.key,
+					),
+					None => <Uniques as Inspect<AccountId>>::collection_attribute(
+						&input.collection,
+						&input.key,
+					),
+				};
+				env.write(&value.encode(), false, None)?;
+				Ok(())
+			},
+			UniquesFunc::CollectionDetails => {
+				let collection: CollectionId = env.read_as()?;
+				env.charge_weight(read_weight)?;
+
+				let details = Class::get(collection).map(|details| CollectionDetails {
+					owner: details.owner,
+					items: details.items,
+				});
+				env.write(&details.encode(), false, None)?;
+				Ok(())
+			},
+		};
+
+		Ok(RetVal::Converging(UniquesError::from(result) as u32))
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{Balances, CollectionDeposit, ItemDeposit, RuntimeOrigin, UNIT};
+	use codec::DecodeAll;
+	use frame_support::{assert_ok, traits::Currency};
+
+	#[test]
+	fn stored_collection_matches_pallet_layout() {
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			let owner = AccountId::from([1; 32]);
+			let admin = AccountId::from([2; 32]);
+			Balances::make_free_balance_be(&owner, 100 * UNIT);
+
+			assert_ok!(Uniques::create(
+				RuntimeOrigin::signed(owner.clone()),
+				7,
+				Lookup::unlookup(admin.clone())
+			));
+			for item in [1, 2] {
+				assert_ok!(Uniques::mint(
+					RuntimeOrigin::signed(admin.clone()),
+					7,
+					item,
+					Lookup::unlookup(owner.clone())
+				));
+			}
+			assert_ok!(Uniques::freeze_collection(RuntimeOrigin::signed(admin.clone()), 7));
+
+			let stored = sp_io::storage::get(&Class::hashed_key_for(7)).unwrap();
+			let details = StoredCollection::decode_all(&mut &stored[..]).unwrap();
+			assert_eq!(details.owner, owner);
+			assert_eq!(details.issuer, admin);
+			assert_eq!(details.admin, admin);
+			assert_eq!(details.freezer, admin);
+			assert_eq!(details.total_deposit, CollectionDeposit::get() + 2 * ItemDeposit::get());
+			assert!(!details.free_holding);
+			assert_eq!(details.items, 2);
+			assert_eq!(details.item_metadatas, 0);
+			assert_eq!(details.attributes, 0);
+			assert!(details.is_frozen);
+		});
+	}
+
+	#[test]
+	fn attribute_inputs_within_limits_fit() {
+		let key = vec![0; <Runtime as pallet_uniques::Config>::KeyLimit::get() as usize];
+		let value = vec![0; <Runtime as pallet_uniques::Config>::ValueLimit::get() as usize];
+		let input = SetAttributeInput {
+			collection: CollectionId::MAX,
+			maybe_item: Some(ItemId::MAX),
+			key: key.clone(),
+			value,
+		};
+		assert!(input.encoded_size() as u32 <= SetAttributeInput::max_len());
+
+		let input =
+			AttributeInput { collection: CollectionId::MAX, maybe_item: Some(ItemId::MAX), key };
+		assert!(input.encoded_size() as u32 <= AttributeInput::max_len());
+	}
+}
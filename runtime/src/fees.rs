@@ -0,0 +1,69 @@
+//! Fee models of the runtime.
+//!
+//! By default fees equal the weight and length of a transaction and never adjust, so that
+//! transactions cost next to nothing. Building with the `production-fees` feature switches to
+//! the polynomial fee and slow adjusting multiplier of Astar and Shiden, so that fee estimates
+//! match production.
+
+#[cfg(feature = "production-fees")]
+pub use production::*;
+#[cfg(not(feature = "production-fees"))]
+pub use zero_friction::*;
+
+#[cfg(not(feature = "production-fees"))]
+mod zero_friction {
+	use crate::Balance;
+	use frame_support::weights::IdentityFee;
+
+	pub type WeightToFee = IdentityFee<Balance>;
+	pub type LengthToFee = IdentityFee<Balance>;
+	pub type FeeMultiplierUpdate = ();
+}
+
+#[cfg(feature = "production-fees")]
+mod production {
+	use crate::{Balance, Runtime, MILLIUNIT};
+	use frame_support::{
+		parameter_types,
+		weights::{
+			constants::ExtrinsicBaseWeight, ConstantMultiplier, WeightToFeeCoefficient,
+			WeightToFeeCoefficients, WeightToFeePolynomial,
+		},
+	};
+	use pallet_transaction_payment::{Multiplier, TargetedFeeAdjustment};
+	use sp_runtime::{traits::Bounded, FixedPointNumber, Perbill, Perquintill};
+
+	parameter_types! {
+		pub const TransactionByteFee: Balance = MILLIUNIT / 100;
+		pub const TargetBlockFullness: Perquintill = Perquintill::from_percent(25);
+		pub AdjustmentVariable: Multiplier = Multiplier::saturating_from_rational(1, 100_000);
+		pub MinimumMultiplier: Multiplier = Multiplier::saturating_from_rational(1, 1_000_000_000u128);
+		pub MaximumMultiplier: Multiplier = Bounded::max_value();
+	}
+
+	/// Maps the base weight of an extrinsic to a tenth of a milli unit, like Astar does for ASTR.
+	pub struct WeightToFee;
+	impl WeightToFeePolynomial for WeightToFee {
+		type Balance = Balance;
+		fn polynomial() -> WeightToFeeCoefficients<Self::Balance> {
+			let p = MILLIUNIT;
+			let q = 10 * Balance::from(ExtrinsicBaseWeight::get().ref_time());
+			smallvec::smallvec![WeightToFeeCoefficient {
+				degree: 1,
+				negative: false,
+				coeff_frac: Perbill::from_rational(p % q, q),
+				coeff_integer: p / q,
+			}]
+		}
+	}
+
+	pub type LengthToFee = ConstantMultiplier<Balance, TransactionByteFee>;
+
+	pub type FeeMultiplierUpdate = TargetedFeeAdjustment<
+		Runtime,
+		TargetBlockFullness,
+		AdjustmentVariable,
+		MinimumMultiplier,
+		MaximumMultiplier,
+	>;
+}
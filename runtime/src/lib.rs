@@ -46,6 +46,13 @@ use frame_system::{
 	EnsureSigned,
 };
 pub use pallet_balances::Call as BalancesCall;
+pub use pallet_contracts_call_filter::Call as ContractsCallFilterCall;
+pub use pallet_dev_oracle::Call as DevOracleCall;
+pub use pallet_dev_randomness::Call as DevRandomnessCall;
+pub use pallet_free_transactions::Call as FreeTransactionsCall;
+pub use pallet_mock_chain_extension::Call as MockChainExtensionCall;
+pub use pallet_pov_limit::Call as PovLimitCall;
+pub use pallet_sudo::Call as SudoCall;
 pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::CurrencyAdapter;
 #[cfg(any(feature = "std", test))]
@@ -53,9 +60,14 @@ pub use sp_runtime::BuildStorage;
 pub use sp_runtime::{Perbill, Permill};
 
 // Chain extensions
-use pallet_chain_extension_dapps_staking::DappsStakingExtension;
+use chain_extensions::{
+	AssetsExtension, BalancesExtension, DappsStakingExtension, MockableExtension, OracleExtension,
+	RandomnessExtension, UniquesExtension,
+};
 
-mod chain_extensions;
+pub mod chain_extensions;
+pub mod fees;
+pub mod profile;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -139,9 +151,8 @@ const NORMAL_DISPATCH_RATIO: Perbill = Perbill::from_percent(75);
 
 const AVERAGE_ON_INITIALIZE_RATIO: Perbill = Perbill::from_percent(10);
 
-/// We allow for 2 seconds of compute with a 6 second average block time, with maximum proof size.
-const MAXIMUM_BLOCK_WEIGHT: Weight =
-	Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND.saturating_mul(2), u64::MAX);
+/// Selected by the build time profile, see [`profile`].
+const MAXIMUM_BLOCK_WEIGHT: Weight = profile::MAXIMUM_BLOCK_WEIGHT;
 
 /// Constant values used within the runtime.
 pub const MILLIUNIT: Balance = 1_000_000_000_000_000;
@@ -176,7 +187,7 @@ parameter_types! {
 		})
 		.avg_block_initialization(AVERAGE_ON_INITIALIZE_RATIO)
 		.build_or_panic();
-	pub const SS58Prefix: u8 = 42;
+	pub const SS58Prefix: u16 = profile::SS58_PREFIX;
 }
 
 // Configure FRAME pallets to include in runtime.
@@ -226,8 +237,9 @@ impl frame_system::Config for Runtime {
 	type AccountData = pallet_balances::AccountData<Balance>;
 	/// Weight information for the extrinsics of this pallet.
 	type SystemWeightInfo = frame_system::weights::SubstrateWeight<Runtime>;
-	/// This is used as an identifier of the chain. 42 is the generic substrate prefix.
-	type SS58Prefix = SS58Prefix;
+	/// This is used as an identifier of the chain. It is set in the chain spec, the prefix of the
+	/// profile being the default.
+	type SS58Prefix = ChainProperties;
 	/// The set code logic, just the default since we're not a parachain.
 	type OnSetCode = ();
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
@@ -235,6 +247,10 @@ impl frame_system::Config for Runtime {
 
 impl pallet_randomness_collective_flip::Config for Runtime {}
 
+impl pallet_dev_randomness::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+}
+
 parameter_types! {
 	pub const MinimumPeriod: u64 = 5;
 }
@@ -256,7 +272,7 @@ impl pallet_balances::Config for Runtime {
 	/// The ubiquitous event type.
 	type RuntimeEvent = RuntimeEvent;
 	type DustRemoval = ();
-	type ExistentialDeposit = ConstU128<500>;
+	type ExistentialDeposit = ConstU128<{ profile::EXISTENTIAL_DEPOSIT }>;
 	type AccountStore = System;
 	type WeightInfo = pallet_balances::weights::SubstrateWeight<Runtime>;
 }
@@ -298,9 +314,38 @@ impl pallet_transaction_payment::Config for Runtime {
 	type RuntimeEvent = RuntimeEvent;
 	type OnChargeTransaction = CurrencyAdapter<Balances, ()>;
 	type OperationalFeeMultiplier = ConstU8<5>;
-	type WeightToFee = IdentityFee<Balance>;
-	type LengthToFee = IdentityFee<Balance>;
-	type FeeMultiplierUpdate = ();
+	/// Selected by the `production-fees` feature, see [`fees`].
+	type WeightToFee = fees::WeightToFee;
+	type LengthToFee = fees::LengthToFee;
+	type FeeMultiplierUpdate = fees::FeeMultiplierUpdate;
+}
+
+impl pallet_asset_tx_payment::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type Fungibles = Assets;
+	/// Fees paid in assets are converted with the rates of `AssetRate` and burned, just like
+	/// fees paid in the native token.
+	type OnChargeAssetTransaction = pallet_asset_tx_payment::FungiblesAdapter<AssetRate, ()>;
+}
+
+impl pallet_free_transactions::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+}
+
+impl pallet_pov_limit::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+}
+
+impl pallet_genesis_contracts::Config for Runtime {}
+
+impl pallet_chain_properties::Config for Runtime {
+	type DefaultSs58Prefix = SS58Prefix;
+}
+
+impl pallet_asset_rate::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetId = AssetId;
+	type Balance = Balance;
 }
 
 impl pallet_sudo::Config for Runtime {
@@ -310,8 +355,8 @@ impl pallet_sudo::Config for Runtime {
 
 // contracts stuffs.
 parameter_types! {
-	pub const DepositPerItem: Balance = deposit(1, 0);
-	pub const DepositPerByte: Balance = deposit(0, 1);
+	pub const DepositPerItem: Balance = profile::contracts_deposit(1, 0);
+	pub const DepositPerByte: Balance = profile::contracts_deposit(0, 1);
 	pub const MaxValueSize: u32 = 16 * 1024;
 	// The lazy deletion runs inside on_initialize.
 	pub DeletionWeightLimit: Weight = AVERAGE_ON_INITIALIZE_RATIO *
@@ -321,34 +366,43 @@ parameter_types! {
 
 impl pallet_contracts::Config for Runtime {
 	type Time = Timestamp;
-	type Randomness = RandomnessCollectiveFlip;
+	/// Seedable randomness, so that contracts relying on it can be tested deterministically.
+	type Randomness = DevRandomness;
 	type Currency = Balances;
 	type RuntimeEvent = RuntimeEvent;
 	type RuntimeCall = RuntimeCall;
-	/// The safest default is to allow no calls at all.
-	///
-	/// Runtimes should whitelist dispatchables that are allowed to be called from contracts
-	/// and make sure they are stable. Dispatchables exposed to contracts are not allowed to
-	/// change because that would break already deployed contracts. The `Call` structure itself
-	/// is not allowed to change the indices of existing pallets, too.
-	type CallFilter = frame_support::traits::Nothing;
+	/// Only the calls allowed in `ContractsCallFilter` can be dispatched, so that contracts can be
+	/// tested under the allowlist of the chain they target.
+	type CallFilter = ContractsCallFilter;
 	type DepositPerItem = DepositPerItem;
 	type DepositPerByte = DepositPerByte;
 	type WeightPrice = pallet_transaction_payment::Pallet<Self>;
 	type WeightInfo = pallet_contracts::weights::SubstrateWeight<Self>;
-	type ChainExtension =
-		(DappsStakingExtension<Self>, pallet_assets_chain_extension::substrate::AssetsExtension);
+	/// Calls with a response registered in `MockChainExtension` are answered with it.
+	type ChainExtension = MockableExtension<(
+		DappsStakingExtension<Self>,
+		AssetsExtension,
+		UniquesExtension,
+		BalancesExtension,
+		RandomnessExtension,
+		OracleExtension,
+	)>;
 	type DeletionQueueDepth = ConstU32<128>;
 	type DeletionWeightLimit = DeletionWeightLimit;
 	type Schedule = Schedule;
-	type CallStack = [pallet_contracts::Frame<Self>; 31];
+	type CallStack = [pallet_contracts::Frame<Self>; profile::CALL_STACK_DEPTH];
 	type AddressGenerator = pallet_contracts::DefaultAddressGenerator;
-	type MaxCodeLen = ConstU32<{ 128 * 1024 }>;
+	type MaxCodeLen = ConstU32<{ profile::MAX_CODE_LEN }>;
 	type MaxStorageKeyLen = ConstU32<128>;
-	type UnsafeUnstableInterface = ConstBool<true>;
+	type UnsafeUnstableInterface = ConstBool<{ profile::UNSAFE_UNSTABLE_INTERFACE }>;
 	type MaxDebugBufferLen = ConstU32<{ 2 * 1024 * 1024 }>;
 }
 
+impl pallet_contracts_call_filter::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type RuntimeCall = RuntimeCall;
+}
+
 parameter_types! {
 	pub const DappsStakingPalletId: PalletId = PalletId(*b"py/dpsst");
 	pub const BlockPerEra: BlockNumber = 60;
@@ -378,6 +432,19 @@ impl pallet_dapps_staking::Config for Runtime {
 	type UnregisteredDappRewardRetention = ConstU32<10>;
 }
 
+impl pallet_dev_oracle::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type AssetId = AssetId;
+	type Price = Balance;
+	type Time = Timestamp;
+	type MaxUpdatesPerBlock = ConstU32<100>;
+}
+
+impl pallet_mock_chain_extension::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type MaxOutputLen = ConstU32<{ 16 * 1024 }>;
+}
+
 parameter_types! {
 	pub const ResourceSymbolLimit: u32 = 10;
 	pub const PartsLimit: u32 = 25;
@@ -426,6 +493,11 @@ impl pallet_uniques::Config for Runtime {
 	type WeightInfo = ();
 }
 
+impl pallet_genesis_uniques::Config for Runtime {
+	type CollectionId = CollectionId;
+	type ItemId = u32;
+}
+
 #[derive(
 	PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug, MaxEncodedLen, scale_info::TypeInfo,
 )]
@@ -471,6 +543,17 @@ construct_runtime!(
 		Contracts: pallet_contracts,
 		DappsStaking: pallet_dapps_staking,
 		Uniques: pallet_uniques,
+		DevRandomness: pallet_dev_randomness,
+		DevOracle: pallet_dev_oracle,
+		MockChainExtension: pallet_mock_chain_extension,
+		ContractsCallFilter: pallet_contracts_call_filter,
+		AssetTxPayment: pallet_asset_tx_payment,
+		AssetRate: pallet_asset_rate,
+		FreeTransactions: pallet_free_transactions,
+		PovLimit: pallet_pov_limit,
+		GenesisContracts: pallet_genesis_contracts,
+		GenesisUniques: pallet_genesis_uniques,
+		ChainProperties: pallet_chain_properties,
 	}
 );
 
@@ -488,8 +571,10 @@ pub type SignedExtra = (
 	frame_system::CheckGenesis<Runtime>,
 	frame_system::CheckEra<Runtime>,
 	frame_system::CheckNonce<Runtime>,
+	pallet_pov_limit::CheckProofSize<Runtime>,
 	frame_system::CheckWeight<Runtime>,
-	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
+	// Fees are waived while `FreeTransactions` is enabled.
+	pallet_free_transactions::ChargeAssetTxPayment<Runtime>,
 );
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic =
@@ -655,6 +740,12 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl chain_extensions::ChainExtensionsApi<Block> for Runtime {
+		fn chain_extensions() -> Vec<chain_extensions::ChainExtensionInfo> {
+			chain_extensions::registered()
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (
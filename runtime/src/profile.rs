@@ -0,0 +1,121 @@
+//! Build time profiles mirroring the limits of production chains.
+//!
+//! Without a profile the runtime is as permissive as possible: large contracts, unstable host
+//! functions and an unlimited proof size. Building with one of the `astar`, `shiden` or `shibuya`
+//! features instead applies the contract limits, deposits, existential deposit, block weights,
+//! SS58 prefix and token symbol of that chain, so that a contract working on the node also works
+//! there.
+//!
+//! The values mirror the runtimes of the chains based on `polkadot-v0.9.37`. All of them use the
+//! default contracts `Schedule`, just like this node. The [`astar`], [`shiden`] and [`shibuya`]
+//! profiles are available whatever the features, for the node to check contracts against them.
+
+use crate::Balance;
+use frame_support::weights::{constants::WEIGHT_REF_TIME_PER_SECOND, Weight};
+
+#[cfg(any(
+	all(feature = "astar", feature = "shiden"),
+	all(feature = "astar", feature = "shibuya"),
+	all(feature = "shiden", feature = "shibuya"),
+))]
+compile_error!("At most one of the `astar`, `shiden` and `shibuya` features can be enabled");
+
+#[cfg(feature = "astar")]
+pub use astar::*;
+#[cfg(not(any(feature = "astar", feature = "shiden", feature = "shibuya")))]
+pub use dev::*;
+#[cfg(feature = "shibuya")]
+pub use shibuya::*;
+#[cfg(feature = "shiden")]
+pub use shiden::*;
+
+/// Block weights and contract limits shared by the parachains.
+mod parachain {
+	use super::*;
+
+	/// Proof size limit of a parachain block on Polkadot and Kusama.
+	const MAX_POV_SIZE: u64 = 5 * 1024 * 1024;
+
+	/// We allow for 0.5 seconds of compute and the proof size limit of the relay chain.
+	pub const MAXIMUM_BLOCK_WEIGHT: Weight =
+		Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND / 2, MAX_POV_SIZE);
+	pub const MAX_CODE_LEN: u32 = 123 * 1024;
+	pub const CALL_STACK_DEPTH: usize = 5;
+	pub const EXISTENTIAL_DEPOSIT: Balance = 1_000_000;
+	pub const SS58_PREFIX: u16 = 5;
+
+	pub const MICRO: Balance = 1_000_000_000_000;
+	pub const MILLI: Balance = 1_000 * MICRO;
+
+	/// Contracts storage deposit, scaled by the initial supply factor of the chain.
+	pub const fn contracts_deposit(items: u32, bytes: u32, supply_factor: Balance) -> Balance {
+		items as Balance * 4 * MILLI * supply_factor +
+			bytes as Balance * 100 * MICRO * supply_factor
+	}
+}
+
+#[cfg(not(any(feature = "astar", feature = "shiden", feature = "shibuya")))]
+mod dev {
+	use super::*;
+
+	/// We allow for 2 seconds of compute with a 6 second average block time, with maximum proof
+	/// size.
+	pub const MAXIMUM_BLOCK_WEIGHT: Weight =
+		Weight::from_parts(WEIGHT_REF_TIME_PER_SECOND.saturating_mul(2), u64::MAX);
+	pub const MAX_CODE_LEN: u32 = 128 * 1024;
+	pub const CALL_STACK_DEPTH: usize = 31;
+	pub const UNSAFE_UNSTABLE_INTERFACE: bool = true;
+	pub const EXISTENTIAL_DEPOSIT: Balance = 500;
+	pub const SS58_PREFIX: u16 = 42;
+	pub const TOKEN_SYMBOL: &str = "UNIT";
+
+	pub const fn contracts_deposit(items: u32, bytes: u32) -> Balance {
+		crate::deposit(items, bytes)
+	}
+}
+
+/// Profile of Astar, the mainnet on Polkadot.
+pub mod astar {
+	pub use super::parachain::{
+		CALL_STACK_DEPTH, EXISTENTIAL_DEPOSIT, MAXIMUM_BLOCK_WEIGHT, MAX_CODE_LEN, SS58_PREFIX,
+	};
+	use crate::Balance;
+
+	pub const UNSAFE_UNSTABLE_INTERFACE: bool = false;
+	pub const TOKEN_SYMBOL: &str = "ASTR";
+
+	pub const fn contracts_deposit(items: u32, bytes: u32) -> Balance {
+		super::parachain::contracts_deposit(items, bytes, 100)
+	}
+}
+
+/// Profile of Shiden, the canary network on Kusama.
+pub mod shiden {
+	pub use super::parachain::{
+		CALL_STACK_DEPTH, EXISTENTIAL_DEPOSIT, MAXIMUM_BLOCK_WEIGHT, MAX_CODE_LEN, SS58_PREFIX,
+	};
+	use crate::Balance;
+
+	pub const UNSAFE_UNSTABLE_INTERFACE: bool = false;
+	pub const TOKEN_SYMBOL: &str = "SDN";
+
+	pub const fn contracts_deposit(items: u32, bytes: u32) -> Balance {
+		super::parachain::contracts_deposit(items, bytes, 1)
+	}
+}
+
+/// Profile of the Shibuya testnet.
+pub mod shibuya {
+	pub use super::parachain::{
+		CALL_STACK_DEPTH, EXISTENTIAL_DEPOSIT, MAXIMUM_BLOCK_WEIGHT, MAX_CODE_LEN, SS58_PREFIX,
+	};
+	use crate::Balance;
+
+	/// Shibuya is the testnet, where unstable host functions are available.
+	pub const UNSAFE_UNSTABLE_INTERFACE: bool = true;
+	pub const TOKEN_SYMBOL: &str = "SBY";
+
+	pub const fn contracts_deposit(items: u32, bytes: u32) -> Balance {
+		super::parachain::contracts_deposit(items, bytes, 100)
+	}
+}